@@ -6,7 +6,7 @@ import {parseNearAmount} from 'near-api-js/lib/utils/format';
 import {NEARType} from '../App';
 
 import "./Home.css";
-import {Project} from '../../../ts/grant';
+import {Project, ProjectStatus, Round} from '../../../ts/grant';
 
 const onSignInOut = (near: NEARType) => {
     if(!near.loaded) return;
@@ -30,6 +30,13 @@ const onDonate = async (near: NEARType) => {
 const onVote = async (near: NEARType, project: Project) => {
     if(!near.loaded) return;
     if(near.authorized) {
+        let balance = await near.grant.storage_balance_of({account_id: near.walletConnection.getAccountId()});
+        if(!balance) {
+            // Votes are stored on the voter's deposit, register it first.
+            let bounds = await near.grant.storage_balance_bounds();
+            await near.grant.storage_deposit({}, {attachedDeposit: bounds.min});
+            return;
+        }
         let tx = await near.grant.vote({project_id: [project.round_id, project.owner], votes: 1}, {attachedDeposit: parseNearAmount("5")});
     } else {
         near.walletConnection.requestSignIn(near.nearConfig.contractName, 'Dorahacks :: Sign In');
@@ -39,12 +46,17 @@ const onVote = async (near: NEARType, project: Project) => {
 const App = ({near}: {near: NEARType}) => {
     const params = useParams();
     const [projects, setProjects] = useState<Project[]>([]);
+    const [currentRound, setCurrentRound] = useState<Round | null>(null);
     
     useEffect(() => {
         if(near.loaded) {
             (async () => {
-                let projects = await near.grant.list_projects({});
-                setProjects(projects);
+                let round = await near.grant.get_current_round();
+                setCurrentRound(round);
+                if(round) {
+                    let projects = await near.grant.list_projects({round_id: round.id, status: ProjectStatus.Approved});
+                    setProjects(projects);
+                }
             })();
         }
     }, [near.loaded]);
@@ -53,8 +65,6 @@ const App = ({near}: {near: NEARType}) => {
         return <>NO</>;
     }
 
-    const currentRound = near.grantConfig.current_round;
-
 
     return (
         <div className={"bg-white text-gray-600 work-sans leading-normal text-base tracking-normal"}>
@@ -101,7 +111,7 @@ const App = ({near}: {near: NEARType}) => {
                         <div className={"w-full container mx-auto flex flex-wrap items-center justify-between mt-0 px-2 py-3"}>
 
                             <a className={"uppercase tracking-wide no-underline hover:no-underline font-bold text-gray-800 text-xl "} href="#" onClick={() => { onDonate(near) } }>
-                                Season { currentRound?.id }  :: Shares {currentRound?.support_area} :: Support {parseInt(currentRound!.support_pool)/1e24} NEAR :: Click to donate
+                                Season { currentRound?.id }  :: Shares {currentRound?.support_area} :: Support {parseInt(currentRound?.support_pool ?? "0")/1e24} NEAR :: Click to donate
                             </a>
                         </div>
                     </nav>
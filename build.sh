@@ -4,3 +4,5 @@ set -e
 cd "`dirname $0`"
 RUSTFLAGS='-C link-arg=-s'  cargo build --all --target wasm32-unknown-unknown --release
 cp $TARGET/wasm32-unknown-unknown/release/grant.wasm ./res/
+cp $TARGET/wasm32-unknown-unknown/release/dummy_ft.wasm ./res/
+cp $TARGET/wasm32-unknown-unknown/release/mock_registry.wasm ./res/
@@ -34,22 +34,30 @@ if [[ $FIRST_TIME == true ]]; then
     near call ${GRANT_CONTRACT} init --accountId kalloc.testnet
 fi
 echo GRANT is ${GRANT_CONTRACT}
-near call $(cat neardev/grant/dev-account) sudo_new_default_round --accountId kalloc.testnet
-near call $(cat neardev/grant/dev-account) new_project '{"name": "NEAR QF Grant", "description": "Built and maintaine near grant", "external_url": "https://8gen.team", "image": "https://picsum.photos/400"}' --accountId a.kalloc.testnet
-near call $(cat neardev/grant/dev-account) new_project '{"name": "NFT Analytics service", "description": "some description, maybe better to use ipfs as data link", "external_url": "https://8gen.team", "image": "https://picsum.photos/400"}' --accountId b.kalloc.testnet
-near call $(cat neardev/grant/dev-account) new_project '{"name": "L2 example implementation", "description": "developer l2 example for near", "external_url": "https://8gen.team", "image": "https://picsum.photos/400"}' --accountId c.kalloc.testnet
-near call $(cat neardev/grant/dev-account) new_project '{"name": "Project #4", "description": "Description #4", "external_url": "https://8gen.team", "image": "https://picsum.photos/400"}' --accountId d.kalloc.testnet
-near call $(cat neardev/grant/dev-account) new_project '{"name": "Project #5", "description": "Description #5", "external_url": "https://8gen.team", "image": "https://picsum.photos/400"}' --accountId e.kalloc.testnet
-near call $(cat neardev/grant/dev-account) new_project '{"name": "Project #6", "description": "Description #6", "external_url": "https://8gen.team", "image": "https://picsum.photos/400"}' --accountId f.kalloc.testnet
-near call $(cat neardev/grant/dev-account) new_project '{"name": "Project #7", "description": "Description #7", "external_url": "https://8gen.team", "image": "https://picsum.photos/400"}' --accountId h.kalloc.testnet
-near call $(cat neardev/grant/dev-account) new_project '{"name": "Project #8", "description": "Description #8", "external_url": "https://8gen.team", "image": "https://picsum.photos/400"}' --accountId i.kalloc.testnet
+START_AT=$(date +%s)
+near call $(cat neardev/grant/dev-account) sudo_schedule_round '{"start_at": '${START_AT}', "end_at": '$((START_AT + 3600))', "params": {"review_duration": 0}}' --accountId kalloc.testnet
+for ACCOUNT in kalloc a.kalloc b.kalloc c.kalloc d.kalloc e.kalloc f.kalloc h.kalloc i.kalloc; do
+    near call $(cat neardev/grant/dev-account) storage_deposit '{"account_id": "'${ACCOUNT}'.testnet"}' --deposit 0.1 --accountId kalloc.testnet
+done
+near call $(cat neardev/grant/dev-account) new_project '{"round_id": 1, "name": "NEAR QF Grant", "description": "Built and maintaine near grant", "external_url": "https://8gen.team", "image": "https://picsum.photos/400"}' --accountId a.kalloc.testnet
+near call $(cat neardev/grant/dev-account) new_project '{"round_id": 1, "name": "NFT Analytics service", "description": "some description, maybe better to use ipfs as data link", "external_url": "https://8gen.team", "image": "https://picsum.photos/400"}' --accountId b.kalloc.testnet
+near call $(cat neardev/grant/dev-account) new_project '{"round_id": 1, "name": "L2 example implementation", "description": "developer l2 example for near", "external_url": "https://8gen.team", "image": "https://picsum.photos/400"}' --accountId c.kalloc.testnet
+near call $(cat neardev/grant/dev-account) new_project '{"round_id": 1, "name": "Project #4", "description": "Description #4", "external_url": "https://8gen.team", "image": "https://picsum.photos/400"}' --accountId d.kalloc.testnet
+near call $(cat neardev/grant/dev-account) new_project '{"round_id": 1, "name": "Project #5", "description": "Description #5", "external_url": "https://8gen.team", "image": "https://picsum.photos/400"}' --accountId e.kalloc.testnet
+near call $(cat neardev/grant/dev-account) new_project '{"round_id": 1, "name": "Project #6", "description": "Description #6", "external_url": "https://8gen.team", "image": "https://picsum.photos/400"}' --accountId f.kalloc.testnet
+near call $(cat neardev/grant/dev-account) new_project '{"round_id": 1, "name": "Project #7", "description": "Description #7", "external_url": "https://8gen.team", "image": "https://picsum.photos/400"}' --accountId h.kalloc.testnet
+near call $(cat neardev/grant/dev-account) new_project '{"round_id": 1, "name": "Project #8", "description": "Description #8", "external_url": "https://8gen.team", "image": "https://picsum.photos/400"}' --accountId i.kalloc.testnet
+for ACCOUNT in a.kalloc b.kalloc c.kalloc d.kalloc e.kalloc f.kalloc h.kalloc i.kalloc; do
+    near call $(cat neardev/grant/dev-account) sudo_approve_project '{"project_id": [1, "'${ACCOUNT}'.testnet"]}' --accountId kalloc.testnet
+done
 near call $(cat neardev/grant/dev-account) vote  '{"project_id": [1, "a.kalloc.testnet"], "votes": 10}' --deposit 100  --accountId kalloc.testnet
-near call $(cat neardev/grant/dev-account) donate '{}' --deposit 5  --accountId kalloc.testnet
+near call $(cat neardev/grant/dev-account) donate '{"round_id": 1}' --deposit 5  --accountId kalloc.testnet
 near call $(cat neardev/grant/dev-account) vote  '{"project_id": [1, "a.kalloc.testnet"], "votes": 5}' --deposit 100  --accountId a.kalloc.testnet
-near call $(cat neardev/grant/dev-account) sudo_update_current_round '{"danger": true, "end_at": '$(date +%s)'}' --accountId kalloc.testnet
-near call $(cat neardev/grant/dev-account) sudo_finish_current_round '{}' --accountId kalloc.testnet
-near call $(cat neardev/grant/dev-account) withdraw '{"project_id": [1, "a.kalloc.testnet"], "amount": "100000000000000000000000"}' --accountId kalloc.testnet
-near view $(cat neardev/grant/dev-account) round '{"round_id": 1}'
-near view $(cat neardev/grant/dev-account) project '{"project_id": [1, "a.kalloc.testnet"]}'
+near call $(cat neardev/grant/dev-account) sudo_update_round '{"round_id": 1, "danger": true, "end_at": '$(date +%s)'}' --accountId kalloc.testnet
+sleep 5
+near call $(cat neardev/grant/dev-account) sudo_finish_round '{"round_id": 1}' --accountId kalloc.testnet
+near call $(cat neardev/grant/dev-account) withdraw '{"project_id": [1, "a.kalloc.testnet"], "amount": "100000000000000000000000"}' --accountId a.kalloc.testnet
+near view $(cat neardev/grant/dev-account) get_round '{"round_id": 1}'
+near view $(cat neardev/grant/dev-account) get_project '{"project_id": [1, "a.kalloc.testnet"]}'
 near view $(cat neardev/grant/dev-account) grant_for '{"project_id": [1, "a.kalloc.testnet"]}'
 
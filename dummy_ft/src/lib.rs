@@ -1,4 +1,3 @@
-use witgen;
 use near_contract_standards::fungible_token::metadata::{
     FungibleTokenMetadata, FT_METADATA_SPEC, FungibleTokenMetadataProvider
 };
@@ -10,7 +9,7 @@ use near_sdk::{
     env, log, near_bindgen, AccountId, BorshStorageKey, PanicOnDefault,
     PromiseOrValue, Balance,
 };
-pub use crate::owner::*;
+
 mod owner;
 
 #[near_bindgen]
@@ -56,19 +55,19 @@ impl Contract {
         assert!(!env::state_exists(), "Already initialized");
         metadata.assert_valid();
         let mut this = Self {
-            owner_id: owner_id.clone().into(),
+            owner_id: owner_id.clone(),
             operators: UnorderedSet::new(StorageKey::Operator),
             token: FungibleToken::new(StorageKey::FungibleToken),
             metadata: LazyOption::new(StorageKey::Metadata, Some(&metadata)),
         };
-        this.token.internal_register_account(&owner_id.clone().into());
-        this.token.internal_deposit(&owner_id.clone().into(), total_supply.into());
+        this.token.internal_register_account(&owner_id.clone());
+        this.token.internal_deposit(&owner_id.clone(), total_supply.into());
         this
     }
 
     pub fn mint(&mut self, receiver_id: AccountId, amount: U128) {
         assert_ne!(amount, U128(0), "must be more than 0.");
-        self.token.internal_deposit(&receiver_id.into(), amount.into());
+        self.token.internal_deposit(&receiver_id, amount.into());
     }
 
     fn on_account_closed(&mut self, account_id: AccountId, balance: Balance) {
@@ -0,0 +1,206 @@
+use near_contract_standards::fungible_token::core::ext_ft_core;
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
+use near_sdk::{env, is_promise_success, near_bindgen, require, AccountId, Balance, Promise};
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// Deposit filing a challenge, returned if the project is disqualified
+/// and taken as platform fee otherwise.
+pub const CHALLENGE_BOND: Balance = ONE_NEAR;
+
+#[witgen::witgen]
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, PartialEq, Debug, Clone)]
+pub enum ChallengeStatus {
+    Open,
+    /// The project was disqualified.
+    Upheld,
+    Dismissed,
+    /// The round was cancelled and the bond returned.
+    Cancelled,
+}
+
+#[witgen::witgen]
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone)]
+pub struct Challenge {
+    pub challenger_id: AccountId,
+    pub reason: String,
+    pub bond: U128,
+    pub created_at: u32,
+    pub status: ChallengeStatus,
+}
+
+impl Contract {
+    /// Takes the project out of matching, its match goes to the other projects of the round.
+    fn internal_disqualify_project(&mut self, project_id: &ProjectId) {
+        let mut project = self.internal_get_project(project_id).expect("ERR_PROJECT_NOT_FOUND");
+        let mut round = self.get_round(project.round_id).expect("ERR_ROUND_NOT_FOUND");
+        require!(round.status == RoundStatus::Active, "ERR_ROUND_NOT_ACTIVE");
+        if matches!(round.matching, MatchingStrategy::PairwiseBounded { .. })
+            && project.index < round.tally_cursor
+        {
+            round.tallied_projects -= 1;
+        }
+        round.projects -= 1;
+        round.support_area -= project.support_area;
+        round.match_area = U128(round.match_area.0 - project.match_area.0);
+        project.support_area = 0;
+        project.match_area = U128(0);
+        project.status = ProjectStatus::Disqualified;
+        self.internal_save_project(project_id, &project);
+        self.internal_save_round(&round);
+        Event::RoundUpdated { round: &round }.emit();
+    }
+
+    /// Returns bonds of open challenges in the round, which is cancelled and left unreviewed.
+    pub(crate) fn internal_cancel_challenges(&mut self, round: &mut Round) {
+        for project_id in std::mem::take(&mut round.open_challenges) {
+            let mut challenge = self.challenges.get(&project_id).unwrap();
+            challenge.status = ChallengeStatus::Cancelled;
+            Promise::new(challenge.challenger_id.clone()).transfer(challenge.bond.0);
+            self.challenges.insert(&project_id, &challenge);
+            Event::ChallengeResolved { project_id: &project_id, challenge: &challenge }.emit();
+        }
+    }
+
+    /// Removes grants of `account_id` to the project, returns their sum.
+    /// Platform fees paid for the votes are collected right away, unless the round
+    /// already collected them when it finished.
+    fn internal_take_grant(
+        &mut self,
+        account_id: &AccountId,
+        project_id: &ProjectId,
+        token_id: &Option<AccountId>,
+    ) -> u128 {
+        let mut record = self.internal_get_vote(account_id, project_id);
+        let amount = record.take_grants(token_id);
+        let fee = record.take_fees(token_id);
+        self.internal_save_vote(account_id, project_id, &record);
+        let mut round = self.get_round(project_id.0).expect("ERR_ROUND_NOT_FOUND");
+        if round.status != RoundStatus::Finished {
+            round.remove_fee(token_id, fee);
+            self.internal_save_round(&round);
+            self.internal_add_fee(token_id, fee);
+        }
+        amount
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Contest an approved project during the review phase of its round,
+    /// `CHALLENGE_BOND` must be attached.
+    #[payable]
+    pub fn challenge_project(&mut self, project_id: ProjectId, reason: String) -> Challenge {
+        self.assert_migrated();
+        let storage_used = env::storage_usage();
+        require!(env::attached_deposit() == CHALLENGE_BOND, "ERR_WRONG_BOND");
+        let project = self.internal_get_project(&project_id).expect("ERR_PROJECT_NOT_FOUND");
+        require!(project.status == ProjectStatus::Approved, "ERR_PROJECT_NOT_APPROVED");
+        let mut round = self.get_round(project.round_id).expect("ERR_ROUND_NOT_FOUND");
+        require!(round.phase() == RoundPhase::Review, "ERR_NOT_REVIEW_PHASE");
+        require!(
+            self.challenges
+                .get(&project_id)
+                .is_none_or(|challenge| challenge.status != ChallengeStatus::Open),
+            "ERR_CHALLENGE_OPEN"
+        );
+        let challenge = Challenge {
+            challenger_id: env::predecessor_account_id(),
+            reason,
+            bond: U128(CHALLENGE_BOND),
+            created_at: (env::block_timestamp_ms() / 1_000) as u32,
+            status: ChallengeStatus::Open,
+        };
+        self.challenges.insert(&project_id, &challenge);
+        round.open_challenges.push(project_id.clone());
+        self.internal_save_round(&round);
+        self.internal_charge_storage(&env::predecessor_account_id(), storage_used);
+        Event::ChallengeFiled { project_id: &project_id, challenge: &challenge }.emit();
+        challenge
+    }
+
+    /// Disqualify the challenged project and return the bond, or dismiss the challenge
+    /// and keep the bond as platform fee. Only can be called by owner or operator.
+    pub fn sudo_resolve_challenge(&mut self, project_id: ProjectId, disqualify: bool) -> Challenge {
+        self.assert_owner_or_operator();
+        self.assert_migrated();
+        let mut challenge = self.challenges.get(&project_id).expect("ERR_CHALLENGE_NOT_FOUND");
+        require!(challenge.status == ChallengeStatus::Open, "ERR_CHALLENGE_RESOLVED");
+        let mut round = self.get_round(project_id.0).expect("ERR_ROUND_NOT_FOUND");
+        round.open_challenges.retain(|id| id != &project_id);
+        self.internal_save_round(&round);
+        if disqualify {
+            self.internal_disqualify_project(&project_id);
+            challenge.status = ChallengeStatus::Upheld;
+            Promise::new(challenge.challenger_id.clone()).transfer(challenge.bond.0);
+        } else {
+            challenge.status = ChallengeStatus::Dismissed;
+            self.fee_amount = U128(self.fee_amount.0 + challenge.bond.0);
+        }
+        self.challenges.insert(&project_id, &challenge);
+        Event::ChallengeResolved { project_id: &project_id, challenge: &challenge }.emit();
+        challenge
+    }
+
+    pub fn get_challenge(&self, project_id: ProjectId) -> Option<Challenge> {
+        self.challenges.get(&project_id)
+    }
+
+    /// Take back votes for a disqualified project, paid in NEAR or in `token_id`.
+    /// Platform fees are not refunded.
+    pub fn claim_grant_refund(
+        &mut self,
+        project_id: ProjectId,
+        token_id: Option<AccountId>,
+    ) -> Promise {
+        self.assert_migrated();
+        let account_id = env::predecessor_account_id();
+        let project = self.internal_get_project(&project_id).expect("ERR_PROJECT_NOT_FOUND");
+        require!(project.status == ProjectStatus::Disqualified, "ERR_PROJECT_NOT_DISQUALIFIED");
+        let storage_used = env::storage_usage();
+        let amount = U128(self.internal_take_grant(&account_id, &project_id, &token_id));
+        require!(amount.0 > 0, "ERR_NOTHING_TO_REFUND");
+        self.internal_remove_votes(&account_id, project_id.0, |voted_project_id| {
+            voted_project_id == &project_id
+        });
+        self.internal_charge_storage(&account_id, storage_used);
+        let transfer = match &token_id {
+            None => Promise::new(account_id.clone()).transfer(amount.0),
+            Some(token_id) => ext_ft_core::ext(token_id.clone())
+                .with_attached_deposit(1)
+                .with_static_gas(GAS_FOR_FT_TRANSFER)
+                .ft_transfer(account_id.clone(), amount, None),
+        };
+        transfer.then(
+            Self::ext(env::current_account_id())
+                .with_static_gas(GAS_FOR_RESOLVE_TRANSFER)
+                .on_claim_grant_refund(project_id, account_id, amount, token_id),
+        )
+    }
+
+    /// Resolves `claim_grant_refund`: on failed transfer `amount` can be claimed again.
+    /// Returns the amount actually refunded.
+    #[private]
+    pub fn on_claim_grant_refund(
+        &mut self,
+        project_id: ProjectId,
+        account_id: AccountId,
+        amount: U128,
+        token_id: Option<AccountId>,
+    ) -> U128 {
+        if !is_promise_success() {
+            self.internal_add_grant(&account_id, &project_id, &token_id, amount.0, 0);
+            return U128(0);
+        }
+        Event::Refund {
+            account_id: &account_id,
+            round_id: project_id.0,
+            amount,
+            token_id: token_id.as_ref(),
+        }
+        .emit();
+        amount
+    }
+}
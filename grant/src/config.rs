@@ -1,7 +1,8 @@
-pub use crate::owner::*;
 use crate::*;
 
-use near_sdk::{near_bindgen, AccountId};
+use near_contract_standards::fungible_token::core::ext_ft_core;
+use near_sdk::{assert_one_yocto, is_promise_success, near_bindgen, AccountId, Promise};
+use near_sdk::{require, PromiseOrValue};
 use serde::{Deserialize, Serialize};
 
 #[near_bindgen]
@@ -10,11 +11,15 @@ use serde::{Deserialize, Serialize};
 pub struct Config {
     version: String,
     owner_id: AccountId,
+    treasury_id: AccountId,
     operators: Vec<AccountId>,
-    current_round: Option<Round>,
+    last_round_id: RoundId,
+    active_rounds: Vec<Round>,
     fee_point: u32,
     default_duration: u32,
     default_vote_cost: U128,
+    default_review_duration: u32,
+    upgrade_delay: u32,
     fee_amount: U128,
     motivation: String,
 }
@@ -25,12 +30,16 @@ impl Contract {
         Config {
             version: env!("CARGO_PKG_VERSION").to_string(),
             owner_id: self.owner_id.clone(),
+            treasury_id: self.treasury_id.clone(),
             operators: self.operators.to_vec(),
             fee_point: self.fee_point,
             fee_amount: self.fee_amount,
             default_duration: self.default_duration,
             default_vote_cost: self.default_vote_cost,
-            current_round: self.rounds.get(&self.current_round_id),
+            default_review_duration: self.default_review_duration,
+            upgrade_delay: self.upgrade_delay,
+            last_round_id: self.last_round_id,
+            active_rounds: self.list_active_rounds(),
             motivation: "In Rust we trust".to_string(),
         }
     }
@@ -40,10 +49,87 @@ impl Contract {
         fee_point: Option<u32>,
         default_duration: Option<u32>,
         default_vote_cost: Option<U128>,
+        default_review_duration: Option<u32>,
     ) {
         self.assert_owner_or_operator();
         update_if_some!(self, default_vote_cost);
+        update_if_some!(self, default_review_duration);
         update_if_some!(self, default_duration);
         update_if_some!(self, fee_point);
+        self.emit_config_changed();
+    }
+
+    /// Send collected platform fees in NEAR, or in `token_id` if given,
+    /// to `receiver_id` or to the treasury. Only can be called by owner.
+    #[payable]
+    pub fn sudo_withdraw_fees(
+        &mut self,
+        amount: U128,
+        receiver_id: Option<AccountId>,
+        token_id: Option<AccountId>,
+    ) -> PromiseOrValue<U128> {
+        assert_one_yocto();
+        self.assert_owner();
+        let receiver_id = receiver_id.unwrap_or_else(|| self.treasury_id.clone());
+        let fee_amount = self.get_fee_amount(token_id.clone());
+        require!(amount.0 <= fee_amount.0, "ERR_TOO_MUCH");
+        self.internal_set_fee_amount(&token_id, fee_amount.0 - amount.0);
+        let transfer = match &token_id {
+            None => Promise::new(receiver_id.clone()).transfer(amount.0),
+            Some(token_id) => ext_ft_core::ext(token_id.clone())
+                .with_attached_deposit(1)
+                .with_static_gas(GAS_FOR_FT_TRANSFER)
+                .ft_transfer(receiver_id.clone(), amount, None),
+        };
+        transfer
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_RESOLVE_TRANSFER)
+                    .on_withdraw_fees(amount, receiver_id, token_id),
+            )
+            .into()
+    }
+
+    /// Resolves `sudo_withdraw_fees`: on failed transfer gives `amount` back to the fees.
+    /// Returns the amount actually paid out.
+    #[private]
+    pub fn on_withdraw_fees(
+        &mut self,
+        amount: U128,
+        receiver_id: AccountId,
+        token_id: Option<AccountId>,
+    ) -> U128 {
+        if !is_promise_success() {
+            self.internal_add_fee(&token_id, amount.0);
+            return U128(0);
+        }
+        Event::FeesWithdrawn { amount, receiver_id: &receiver_id, token_id: token_id.as_ref() }
+            .emit();
+        amount
+    }
+
+    /// Collected platform fees in NEAR or in `token_id`.
+    pub fn get_fee_amount(&self, token_id: Option<AccountId>) -> U128 {
+        match token_id {
+            None => self.fee_amount,
+            Some(token_id) => self.token_fee_amounts.get(&token_id).unwrap_or(U128(0)),
+        }
+    }
+}
+
+impl Contract {
+    /// Adds `amount` to the collected platform fees in NEAR or in `token_id`.
+    pub(crate) fn internal_add_fee(&mut self, token_id: &Option<AccountId>, amount: u128) {
+        let fee_amount = self.get_fee_amount(token_id.clone());
+        self.internal_set_fee_amount(token_id, fee_amount.0 + amount);
+    }
+
+    fn internal_set_fee_amount(&mut self, token_id: &Option<AccountId>, amount: u128) {
+        match token_id {
+            None => self.fee_amount = U128(amount),
+            Some(token_id) => {
+                self.token_fee_amounts.insert(token_id, &U128(amount));
+            }
+        }
     }
 }
@@ -0,0 +1,140 @@
+use near_sdk::{env, json_types::U128, AccountId};
+use serde::Serialize;
+
+use crate::*;
+
+pub const EVENT_STANDARD: &str = "grant";
+pub const EVENT_VERSION: &str = "1.0.0";
+
+/// NEP-297 events of the grant contract.
+/// `token_id` is set when amounts are in a fungible token instead of NEAR.
+#[derive(Serialize)]
+#[serde(tag = "event", content = "data", rename_all = "snake_case")]
+pub enum Event<'a> {
+    ProjectCreated {
+        project_id: &'a ProjectId,
+        project: &'a Project,
+        storage_cost: U128,
+    },
+    ProjectUpdated {
+        project_id: &'a ProjectId,
+        project: &'a Project,
+    },
+    ProjectReviewed {
+        project_id: &'a ProjectId,
+        status: &'a ProjectStatus,
+        reason: Option<&'a String>,
+    },
+    Vote {
+        voter_id: &'a AccountId,
+        project_id: &'a ProjectId,
+        votes: u64,
+        cost: U128,
+        platform_fee: U128,
+        storage_cost: U128,
+        refund: U128,
+        token_id: Option<&'a AccountId>,
+    },
+    VoteRefunded {
+        voter_id: &'a AccountId,
+        project_id: &'a ProjectId,
+        amount: U128,
+        token_id: Option<&'a AccountId>,
+    },
+    Donation {
+        donor_id: &'a AccountId,
+        round_id: RoundId,
+        amount: U128,
+        platform_fee: U128,
+        storage_cost: U128,
+        token_id: Option<&'a AccountId>,
+    },
+    Withdraw {
+        project_id: &'a ProjectId,
+        receiver_id: &'a AccountId,
+        amount: U128,
+        token_id: Option<&'a AccountId>,
+    },
+    Refund {
+        account_id: &'a AccountId,
+        round_id: RoundId,
+        amount: U128,
+        token_id: Option<&'a AccountId>,
+    },
+    ChallengeFiled {
+        project_id: &'a ProjectId,
+        challenge: &'a Challenge,
+    },
+    ChallengeResolved {
+        project_id: &'a ProjectId,
+        challenge: &'a Challenge,
+    },
+    RoundCreated {
+        round: &'a Round,
+    },
+    RoundUpdated {
+        round: &'a Round,
+    },
+    RoundFinished {
+        round: &'a Round,
+    },
+    RoundRolledOver {
+        round: &'a Round,
+    },
+    ConfigChanged {
+        owner_id: &'a AccountId,
+        treasury_id: &'a AccountId,
+        fee_point: u32,
+        default_duration: u32,
+        default_vote_cost: U128,
+        default_review_duration: u32,
+        upgrade_delay: u32,
+    },
+    OperatorsAdded {
+        operators: &'a [AccountId],
+    },
+    OperatorsRemoved {
+        operators: &'a [AccountId],
+    },
+    UpgradeStaged {
+        upgrade: &'a StagedUpgrade,
+    },
+    UpgradeDeployed {
+        upgrade: &'a StagedUpgrade,
+    },
+    FeesWithdrawn {
+        amount: U128,
+        receiver_id: &'a AccountId,
+        token_id: Option<&'a AccountId>,
+    },
+}
+
+#[derive(Serialize)]
+struct EventLog<'a> {
+    standard: &'static str,
+    version: &'static str,
+    #[serde(flatten)]
+    event: &'a Event<'a>,
+}
+
+impl Event<'_> {
+    pub fn emit(&self) {
+        let log = EventLog { standard: EVENT_STANDARD, version: EVENT_VERSION, event: self };
+        env::log_str(&format!("EVENT_JSON:{}", serde_json::to_string(&log).unwrap()));
+    }
+}
+
+impl Contract {
+    pub(crate) fn emit_config_changed(&self) {
+        Event::ConfigChanged {
+            owner_id: &self.owner_id,
+            treasury_id: &self.treasury_id,
+            fee_point: self.fee_point,
+            default_duration: self.default_duration,
+            default_vote_cost: self.default_vote_cost,
+            default_review_duration: self.default_review_duration,
+            upgrade_delay: self.upgrade_delay,
+        }
+        .emit();
+    }
+}
@@ -2,35 +2,103 @@ use std::collections::HashMap;
 
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 
-pub use crate::project::{Project, ProjectId};
-pub use crate::round::{Round, RoundId};
-use near_sdk::collections::{TreeMap, UnorderedMap, UnorderedSet};
+pub use crate::challenge::{Challenge, ChallengeStatus, CHALLENGE_BOND};
+pub use crate::events::Event;
+pub use crate::matching::{MatchingStrategy, MAX_VOTERS_PER_PROJECT, TALLY_PAIRS_PER_CALL};
+use crate::migrate::Migration;
+pub use crate::migrate::{
+    state_version, ContractV1, ProjectV1, RoundV1, StateVersion, VersionedProject, VersionedRound,
+    MIGRATE_ENTRIES_PER_CALL,
+};
+pub use crate::owner::StagedUpgrade;
+pub use crate::project::{
+    Project, ProjectId, ProjectRevision, ProjectStatus, MAX_PROJECT_REVISIONS,
+};
+pub use crate::ranking::{ProjectCursor, ProjectPage, ProjectSort};
+pub use crate::registry::{ext_registry, Registry};
+pub use crate::result::RoundResult;
+pub use crate::rollover::{Rollover, RolloverTarget};
+pub use crate::round::{
+    Round, RoundId, RoundParams, RoundPhase, RoundPhases, RoundStatus, TokenPool,
+};
+pub use crate::storage::StorageAccount;
+pub use crate::token::FtMessage;
+pub use crate::voter::{AccountContribution, AccountVote, ProjectVoter, RoundVotes, VoteRecord};
+use near_sdk::collections::{LookupMap, TreeMap, UnorderedMap, UnorderedSet};
 use near_sdk::ONE_NEAR;
-use near_sdk::{env, json_types::U128, near_bindgen, AccountId, BorshStorageKey, PanicOnDefault};
-use witgen;
+use near_sdk::{
+    env, json_types::U128, near_bindgen, AccountId, BorshStorageKey, Gas, PanicOnDefault,
+};
 
+mod challenge;
 mod config;
+mod events;
 mod macros;
+mod matching;
+mod migrate;
 mod owner;
 mod project;
+mod ranking;
+mod refund;
+mod registry;
+mod result;
+mod rollover;
 mod round;
+mod storage;
+mod token;
+mod voter;
+
+pub const GAS_FOR_RESOLVE_TRANSFER: Gas = Gas(10_000_000_000_000);
+pub const GAS_FOR_FT_TRANSFER: Gas = Gas(10_000_000_000_000);
+pub const GAS_FOR_IS_ELIGIBLE: Gas = Gas(5_000_000_000_000);
+pub const GAS_FOR_RESOLVE_VOTE: Gas = Gas(60_000_000_000_000);
+/// Least gas left in `on_eligible_vote` to apply a vote, on top of the pair weights.
+pub const GAS_FOR_APPLY_VOTE: Gas = Gas(40_000_000_000_000);
+/// Gas to update the weight of one pair of voters in `PairwiseBounded` rounds.
+pub const GAS_FOR_PAIR_WEIGHT: Gas = Gas(500_000_000_000);
+/// Seconds between the end of voting and payouts of rounds without phases, for challenges.
+pub const DEFAULT_REVIEW_DURATION: u32 = 60 * 60 * 24 * 3;
+/// Least gas for `migrate` after `deploy_upgrade`, it also gets all unused gas.
+pub const GAS_FOR_MIGRATE: Gas = Gas(20_000_000_000_000);
 
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
 pub struct Contract {
     owner_id: AccountId,
+    treasury_id: AccountId,
     operators: UnorderedSet<AccountId>,
-    projects: TreeMap<ProjectId, Project>,
+    projects: TreeMap<ProjectId, VersionedProject>,
     rounds_for_owner: UnorderedMap<AccountId, UnorderedSet<RoundId>>,
-    grants: UnorderedMap<AccountId, HashMap<ProjectId, U128>>,
-    votes: UnorderedMap<AccountId, HashMap<ProjectId, u64>>,
+    /// Votes and grants by voter and project, no entry grows with the number of votes.
+    vote_records: LookupMap<(AccountId, ProjectId), VoteRecord>,
+    round_votes: LookupMap<(AccountId, RoundId), RoundVotes>,
     round_projects: UnorderedMap<RoundId, UnorderedSet<ProjectId>>,
-    rounds: TreeMap<RoundId, Round>,
-    current_round_id: RoundId,
+    project_voters: UnorderedMap<ProjectId, UnorderedMap<AccountId, u64>>,
+    pair_weights: LookupMap<(RoundId, AccountId, AccountId), U128>,
+    storage_accounts: LookupMap<AccountId, StorageAccount>,
+    rounds: TreeMap<RoundId, VersionedRound>,
+    last_round_id: RoundId,
     fee_point: u32,
     fee_amount: U128,
+    token_fee_amounts: UnorderedMap<AccountId, U128>,
     default_duration: u32,
     default_vote_cost: U128,
+    default_review_duration: u32,
+    upgrade_delay: u32,
+    staged_upgrade: Option<StagedUpgrade>,
+    project_revisions: LookupMap<ProjectId, Vec<ProjectRevision>>,
+    reserve: U128,
+    token_reserves: UnorderedMap<AccountId, U128>,
+    donations: LookupMap<(AccountId, RoundId), U128>,
+    token_donations: LookupMap<(AccountId, RoundId, AccountId), U128>,
+    /// Calendar of scheduled rounds by `start_at`, they never overlap.
+    schedule: TreeMap<u32, RoundId>,
+    challenges: LookupMap<ProjectId, Challenge>,
+    project_rankings: LookupMap<(RoundId, ProjectSort), TreeMap<(u128, AccountId), ()>>,
+    round_results: LookupMap<ProjectId, RoundResult>,
+    ranked_results: LookupMap<(RoundId, u32), ProjectId>,
+    /// Old state still to move after an upgrade, see `sudo_migrate`.
+    migration: Option<Migration>,
 }
 
 #[derive(BorshSerialize, BorshStorageKey)]
@@ -44,6 +112,27 @@ pub enum StorageKey {
     Projects,
     AccountRounds { account_id: AccountId },
     ProjectsPerRound { round_id: RoundId },
+    TokenGrants,
+    TokenFeeAmounts,
+    StorageAccounts,
+    ProjectVoters,
+    VotersPerProject { project_id: ProjectId },
+    PairWeights,
+    ProjectRevisions,
+    TokenReserves,
+    Donations,
+    TokenDonations,
+    Schedule,
+    Challenges,
+    ProjectRankings,
+    ProjectRanking { round_id: RoundId, sort: ProjectSort },
+    RoundResults,
+    RankedResults,
+    VoteRecords,
+    RoundVotes,
+    RoundDonations,
+    RoundTokenDonations,
+    VotedProjects { account_id: AccountId, round_id: RoundId },
 }
 
 #[near_bindgen]
@@ -51,20 +140,40 @@ impl Contract {
     #[init]
     pub fn init() -> Self {
         assert!(!env::state_exists(), "Already initialized");
+        migrate::write_state_version();
         Self {
             owner_id: env::predecessor_account_id(),
+            treasury_id: env::predecessor_account_id(),
             operators: UnorderedSet::new(StorageKey::Operators),
             fee_point: 500, // 5.00%
             fee_amount: 0.into(),
+            token_fee_amounts: UnorderedMap::new(StorageKey::TokenFeeAmounts),
             default_duration: 60 * 60 * 24 * 31,
             default_vote_cost: (ONE_NEAR / 10).into(),
-            current_round_id: 0,
-            votes: UnorderedMap::new(StorageKey::Votes),
-            grants: UnorderedMap::new(StorageKey::Grants),
+            default_review_duration: DEFAULT_REVIEW_DURATION,
+            upgrade_delay: 0,
+            staged_upgrade: None,
+            project_revisions: LookupMap::new(StorageKey::ProjectRevisions),
+            reserve: U128(0),
+            token_reserves: UnorderedMap::new(StorageKey::TokenReserves),
+            donations: LookupMap::new(StorageKey::RoundDonations),
+            token_donations: LookupMap::new(StorageKey::RoundTokenDonations),
+            schedule: TreeMap::new(StorageKey::Schedule),
+            challenges: LookupMap::new(StorageKey::Challenges),
+            project_rankings: LookupMap::new(StorageKey::ProjectRankings),
+            round_results: LookupMap::new(StorageKey::RoundResults),
+            ranked_results: LookupMap::new(StorageKey::RankedResults),
+            last_round_id: 0,
+            vote_records: LookupMap::new(StorageKey::VoteRecords),
+            round_votes: LookupMap::new(StorageKey::RoundVotes),
             rounds: TreeMap::new(StorageKey::Rounds),
             rounds_for_owner: UnorderedMap::new(StorageKey::RoundsForOwner),
             round_projects: UnorderedMap::new(StorageKey::RoundProjects),
+            project_voters: UnorderedMap::new(StorageKey::ProjectVoters),
+            pair_weights: LookupMap::new(StorageKey::PairWeights),
+            storage_accounts: LookupMap::new(StorageKey::StorageAccounts),
             projects: TreeMap::new(StorageKey::Projects),
+            migration: None,
         }
     }
 }
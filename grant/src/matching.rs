@@ -0,0 +1,236 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
+use near_sdk::{near_bindgen, require, AccountId};
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+pub use uint_types::U256;
+
+#[allow(clippy::all)]
+mod uint_types {
+    uint::construct_uint! {
+        pub struct U256(4);
+    }
+}
+
+/// Fixed point scale of square roots of paid weights.
+const SQRT_SCALE: u128 = 1_000_000_000_000;
+
+/// Most pairs of voters `sudo_tally_round` scores in one call.
+pub const TALLY_PAIRS_PER_CALL: u32 = 500;
+
+/// Default and most `max_voters_per_project` of a `PairwiseBounded` round, keeps the gas
+/// of a vote that updates a pair weight with every other voter within a call.
+pub const MAX_VOTERS_PER_PROJECT: u32 = 200;
+
+#[witgen::witgen]
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, PartialEq, Debug, Clone)]
+pub enum MatchingStrategy {
+    /// Area of pairwise co-votes, `votes * (total_votes - voted)` on every vote.
+    Pairwise,
+    /// Classic CLR, `(Σ√c)² − Σc` over contributions of every voter.
+    Clr,
+    /// Pairwise coordination subsidies, every pair of voters adds `√(c_i c_j) * m / (m + k_ij)`
+    /// where `k_ij` is the pair's co-contribution across the round and `m` is in paid weight.
+    /// Projects are scored by `sudo_tally_round` once voting is over and take at most
+    /// `max_voters_per_project` voters, as every vote updates the weight of a pair with each.
+    PairwiseBounded { m: u64 },
+}
+
+pub fn isqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+    let mut x = value;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+pub fn mul_div(a: u128, b: u128, c: u128) -> u128 {
+    (U256::from(a) * U256::from(b) / U256::from(c)).as_u128()
+}
+
+/// Scaled square root of the contribution of `votes` votes, measured in paid weight.
+fn sqrt_weight(votes: u64) -> u128 {
+    let weight = votes as u128 * (votes as u128 + 1) / 2;
+    isqrt(weight * SQRT_SCALE)
+}
+
+fn pair_key(round_id: RoundId, a: &AccountId, b: &AccountId) -> (RoundId, AccountId, AccountId) {
+    if a < b {
+        (round_id, a.clone(), b.clone())
+    } else {
+        (round_id, b.clone(), a.clone())
+    }
+}
+
+impl Round {
+    /// Whether match areas of all projects are known.
+    pub fn is_tallied(&self) -> bool {
+        match self.matching {
+            MatchingStrategy::PairwiseBounded { .. } => self.tallied_projects == self.projects,
+            _ => true,
+        }
+    }
+
+    /// Part of `pool` matched to the project.
+    pub fn matched(&self, project: &Project, pool: u128) -> u128 {
+        match self.matching {
+            MatchingStrategy::Pairwise => {
+                if self.support_area == 0 {
+                    return 0;
+                }
+                project.support_area as u128 * pool / self.support_area as u128
+            }
+            MatchingStrategy::Clr | MatchingStrategy::PairwiseBounded { .. } => {
+                if !self.is_tallied() || self.match_area.0 == 0 {
+                    return 0;
+                }
+                mul_div(project.match_area.0, pool, self.match_area.0)
+            }
+        }
+    }
+
+    /// Part of the NEAR pool, or of `token_id` pool, matched to the project.
+    /// `matching_cap` of the round limits the NEAR match.
+    pub fn matched_of(&self, project: &Project, token_id: &Option<AccountId>) -> u128 {
+        let matched = self.matched(project, self.support_pool_of(token_id));
+        match (token_id, &self.params.matching_cap) {
+            (None, Some(cap)) => matched.min(cap.0),
+            _ => matched,
+        }
+    }
+
+    /// Updates match areas of `Clr` rounds after voter went from `voted` to `votes` votes.
+    pub(crate) fn update_match_area(&mut self, project: &mut Project, voted: u64, votes: u64) {
+        if self.matching != MatchingStrategy::Clr {
+            return;
+        }
+        let (before, after) = (sqrt_weight(voted), sqrt_weight(votes));
+        project.sqrt_sum = U128(project.sqrt_sum.0 - before + after);
+        project.square_sum = U128(project.square_sum.0 - before * before + after * after);
+        let area = project.sqrt_sum.0 * project.sqrt_sum.0 - project.square_sum.0;
+        self.match_area = U128(self.match_area.0 - project.match_area.0 + area);
+        project.match_area = U128(area);
+    }
+}
+
+impl Contract {
+    /// Moves co-contributions of `voter` with other voters of the project
+    /// after they went from `voted` to `votes` votes.
+    pub(crate) fn internal_update_pair_weights(
+        &mut self,
+        round: &Round,
+        project_id: &ProjectId,
+        voter: &AccountId,
+        voted: u64,
+        votes: u64,
+    ) {
+        if !matches!(round.matching, MatchingStrategy::PairwiseBounded { .. }) {
+            return;
+        }
+        let (before, after) = (sqrt_weight(voted), sqrt_weight(votes));
+        let voters = self.project_voters.get(project_id).expect("ERR_PROJECT_NOT_FOUND");
+        for (other, other_votes) in voters.iter() {
+            if &other == voter {
+                continue;
+            }
+            let key = pair_key(round.id, voter, &other);
+            let other_sqrt = sqrt_weight(other_votes);
+            let weight = self.pair_weights.get(&key).map_or(0, |weight| weight.0);
+            self.pair_weights
+                .insert(&key, &U128(weight - before * other_sqrt + after * other_sqrt));
+        }
+    }
+
+    /// Adds up to `pairs` more pairs of voters of the project to `tally_area` of the round,
+    /// starting from `tally_pair`. Returns whether all pairs of the project are scored.
+    fn internal_bounded_area(
+        &self,
+        round: &mut Round,
+        project_id: &ProjectId,
+        m: u64,
+        pairs: &mut u32,
+    ) -> bool {
+        let m = m as u128 * SQRT_SCALE;
+        let voters = self.project_voters.get(project_id).expect("ERR_PROJECT_NOT_FOUND");
+        let (keys, values) = (voters.keys_as_vector(), voters.values_as_vector());
+        let count = keys.len() as u32;
+        let (mut i, mut j) = round.tally_pair;
+        let mut area = round.tally_area.0;
+        while i < count {
+            let voter = keys.get(i as u64).unwrap();
+            let sqrt = sqrt_weight(values.get(i as u64).unwrap());
+            j = j.max(i + 1);
+            while j < count {
+                if *pairs == 0 {
+                    round.tally_pair = (i, j);
+                    round.tally_area = U128(area);
+                    return false;
+                }
+                let other = keys.get(j as u64).unwrap();
+                let other_sqrt = sqrt_weight(values.get(j as u64).unwrap());
+                let weight = self
+                    .pair_weights
+                    .get(&pair_key(round.id, &voter, &other))
+                    .map_or(0, |weight| weight.0);
+                area += mul_div(sqrt * other_sqrt, m, m + weight);
+                *pairs -= 1;
+                j += 1;
+            }
+            i += 1;
+            j = 0;
+        }
+        round.tally_area = U128(area);
+        true
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Scores up to `limit` more projects of a `PairwiseBounded` round after voting ended, skipping
+    /// projects which are not approved. One call scores at most `TALLY_PAIRS_PER_CALL` pairs
+    /// of voters and continues a project where the last call stopped.
+    /// Call until `tallied_projects` reaches `projects`, matching is paid out after that.
+    pub fn sudo_tally_round(&mut self, round_id: RoundId, limit: Option<u32>) -> Round {
+        self.assert_owner_or_operator();
+        self.assert_migrated();
+        let mut round = self.get_round(round_id).expect("ERR_ROUND_NOT_FOUND");
+        require!(
+            matches!(round.phase(), RoundPhase::Review | RoundPhase::Payout),
+            "ERR_VOTING_NOT_ENDED"
+        );
+        let m = match round.matching {
+            MatchingStrategy::PairwiseBounded { m } => Some(m),
+            _ => None,
+        }
+        .expect("ERR_NOTHING_TO_TALLY");
+        let projects = self.round_projects.get(&round_id).expect("ERR_ROUND_NOT_FOUND");
+        let end = (projects.len() as u32)
+            .min(round.tally_cursor.saturating_add(limit.unwrap_or(u32::MAX)));
+        let mut pairs = TALLY_PAIRS_PER_CALL;
+        while round.tally_cursor < end {
+            let project_id = projects.as_vector().get(round.tally_cursor as u64).unwrap();
+            let mut project = self.internal_get_project(&project_id).unwrap();
+            if project.status == ProjectStatus::Approved {
+                if !self.internal_bounded_area(&mut round, &project_id, m, &mut pairs) {
+                    break;
+                }
+                project.match_area = round.tally_area;
+                round.match_area = U128(round.match_area.0 + project.match_area.0);
+                round.tallied_projects += 1;
+                self.internal_save_project(&project_id, &project);
+            }
+            round.tally_cursor += 1;
+            round.tally_pair = (0, 0);
+            round.tally_area = U128(0);
+        }
+        self.internal_save_round(&round);
+        Event::RoundUpdated { round: &round }.emit();
+        round
+    }
+}
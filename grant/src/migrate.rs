@@ -0,0 +1,511 @@
+use crate::*;
+
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::{LookupMap, TreeMap, UnorderedMap, UnorderedSet};
+use near_sdk::{env, json_types::U128, near_bindgen, require, AccountId, IntoStorageKey};
+
+/// Storage key of the state layout version, missing in state of the first release.
+const STATE_VERSION_KEY: &[u8] = b"STATE_VERSION";
+
+#[derive(BorshDeserialize, BorshSerialize, PartialEq, Debug)]
+pub enum StateVersion {
+    V1,
+    V2,
+}
+
+pub const CURRENT_STATE_VERSION: StateVersion = StateVersion::V2;
+
+/// Entries of the old state moved by one `sudo_migrate` call by default.
+pub const MIGRATE_ENTRIES_PER_CALL: u32 = 100;
+
+/// Layout version of the stored state.
+pub fn state_version() -> StateVersion {
+    env::storage_read(STATE_VERSION_KEY)
+        .map(|version| StateVersion::try_from_slice(&version).expect("ERR_WRONG_STATE_VERSION"))
+        .unwrap_or(StateVersion::V1)
+}
+
+pub(crate) fn write_state_version() {
+    env::storage_write(STATE_VERSION_KEY, &CURRENT_STATE_VERSION.try_to_vec().unwrap());
+}
+
+/// Contract state of the first release.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ContractV1 {
+    pub owner_id: AccountId,
+    pub operators: UnorderedSet<AccountId>,
+    pub projects: TreeMap<ProjectId, ProjectV1>,
+    pub rounds_for_owner: UnorderedMap<AccountId, UnorderedSet<RoundId>>,
+    pub grants: UnorderedMap<AccountId, HashMap<ProjectId, U128>>,
+    pub votes: UnorderedMap<AccountId, HashMap<ProjectId, u64>>,
+    pub round_projects: UnorderedMap<RoundId, UnorderedSet<ProjectId>>,
+    pub rounds: TreeMap<RoundId, RoundV1>,
+    pub current_round_id: RoundId,
+    pub fee_point: u32,
+    pub fee_amount: U128,
+    pub default_duration: u32,
+    pub default_vote_cost: U128,
+}
+
+/// Old state left after `migrate`, moved in pages by `sudo_migrate`.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Migration {
+    /// Steps still to run, the first one is in progress.
+    steps: Vec<MigrationStep>,
+    votes: UnorderedMap<AccountId, HashMap<ProjectId, u64>>,
+    grants: UnorderedMap<AccountId, HashMap<ProjectId, U128>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Clone)]
+enum MigrationStep {
+    /// Rounds of the first release after `cursor` to tag as `V1`.
+    Rounds { cursor: Option<RoundId> },
+    /// Projects of the first release after `cursor` to tag as `V1`.
+    Projects { cursor: Option<ProjectId> },
+    /// Votes and grants to move to vote records.
+    Votes,
+    /// Rounds after `cursor` to put on the calendar when still open, `first` before them.
+    Schedule { first: Option<RoundId>, cursor: Option<RoundId> },
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct RoundV1 {
+    pub created_at: u32,
+    pub end_at: u32,
+    pub pure_support_pool: U128,
+    pub id: RoundId,
+    pub start_at: u32,
+    pub support_area: u64,
+    pub support_pool: U128,
+    pub vote_cost: U128,
+    pub projects: u32,
+    pub status: RoundStatus,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ProjectV1 {
+    pub name: String,
+    pub description: String,
+    pub external_url: String,
+    pub image: String,
+    pub round_id: RoundId,
+    pub owner: AccountId,
+    pub created_at: u32,
+    pub total_votes: u64,
+    pub grants: U128,
+    pub support_area: u64,
+    pub withdrawn: U128,
+}
+
+/// Stored round, older layouts are upgraded when read.
+/// Released layouts are frozen, a new one is added before `Current` when `Round` changes.
+#[derive(BorshDeserialize, BorshSerialize)]
+#[allow(clippy::large_enum_variant)]
+pub enum VersionedRound {
+    V1(RoundV1),
+    Current(Round),
+}
+
+/// Stored project, older layouts are upgraded when read.
+/// Released layouts are frozen, a new one is added before `Current` when `Project` changes.
+#[derive(BorshDeserialize, BorshSerialize)]
+#[allow(clippy::large_enum_variant)]
+pub enum VersionedProject {
+    V1(ProjectV1),
+    Current(Project),
+}
+
+impl From<RoundV1> for Round {
+    fn from(round: RoundV1) -> Self {
+        Round {
+            created_at: round.created_at,
+            end_at: round.end_at,
+            pure_support_pool: round.pure_support_pool,
+            id: round.id,
+            start_at: round.start_at,
+            support_area: round.support_area,
+            support_pool: round.support_pool,
+            vote_cost: round.vote_cost,
+            projects: round.projects,
+            status: round.status,
+            accepted_tokens: HashMap::new(),
+            token_pools: HashMap::new(),
+            matching: MatchingStrategy::Pairwise,
+            match_area: U128(0),
+            tallied_projects: 0,
+            tally_cursor: 0,
+            tally_pair: (0, 0),
+            tally_area: U128(0),
+            funded: U128(0),
+            token_funded: HashMap::new(),
+            ranked_projects: 0,
+            rank_cursor: None,
+            matched: U128(0),
+            token_matched: HashMap::new(),
+            fees: U128(0),
+            token_fees: HashMap::new(),
+            registry_id: None,
+            rollover: None,
+            rolled_over_from: vec![],
+            params: RoundParams {
+                review_duration: Some(DEFAULT_REVIEW_DURATION),
+                ..Default::default()
+            },
+            open_challenges: vec![],
+        }
+    }
+}
+
+impl From<ProjectV1> for Project {
+    fn from(project: ProjectV1) -> Self {
+        Project {
+            name: project.name,
+            description: project.description,
+            external_url: project.external_url,
+            image: project.image,
+            round_id: project.round_id,
+            owner: project.owner,
+            created_at: project.created_at,
+            total_votes: project.total_votes,
+            grants: project.grants,
+            support_area: project.support_area,
+            withdrawn: project.withdrawn,
+            beneficiary: None,
+            delegates: vec![],
+            token_grants: HashMap::new(),
+            token_withdrawn: HashMap::new(),
+            match_area: U128(0),
+            sqrt_sum: U128(0),
+            square_sum: U128(0),
+            status: ProjectStatus::Approved,
+            review_reason: None,
+            index: 0,
+        }
+    }
+}
+
+impl From<VersionedRound> for Round {
+    fn from(round: VersionedRound) -> Self {
+        match round {
+            VersionedRound::V1(round) => round.into(),
+            VersionedRound::Current(round) => round,
+        }
+    }
+}
+
+impl From<VersionedProject> for Project {
+    fn from(project: VersionedProject) -> Self {
+        match project {
+            VersionedProject::V1(project) => project.into(),
+            VersionedProject::Current(project) => project,
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Upgrades the stored state to the current layout, call it right after deploying new code.
+    /// Only collection handles are moved here, entries follow in pages of `sudo_migrate`.
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let contract = match state_version() {
+            StateVersion::V1 => Self::migrate_v1(env::state_read().expect("ERR_NO_STATE")),
+            StateVersion::V2 => env::state_read().expect("ERR_NO_STATE"),
+        };
+        write_state_version();
+        contract
+    }
+
+    /// Moves at most `limit` entries of the old state, `MIGRATE_ENTRIES_PER_CALL` by default.
+    /// Call until it returns true, calls which read old state fail with `ERR_MIGRATION_PENDING`
+    /// meanwhile.
+    pub fn sudo_migrate(&mut self, limit: Option<u32>) -> bool {
+        self.assert_owner_or_operator();
+        let mut migration = match self.migration.take() {
+            Some(migration) => migration,
+            None => return true,
+        };
+        let mut limit = limit.unwrap_or(MIGRATE_ENTRIES_PER_CALL);
+        require!(limit > 0, "ERR_ZERO_LIMIT");
+        while limit > 0 && !migration.steps.is_empty() {
+            let done = match migration.steps[0].clone() {
+                MigrationStep::Rounds { cursor } => {
+                    self.internal_migrate_rounds(&mut migration, cursor, &mut limit)
+                }
+                MigrationStep::Projects { cursor } => {
+                    self.internal_migrate_projects(&mut migration, cursor, &mut limit)
+                }
+                MigrationStep::Votes => self.internal_migrate_votes(&mut migration, &mut limit),
+                MigrationStep::Schedule { first, cursor } => {
+                    self.internal_migrate_schedule(&mut migration, first, cursor, &mut limit)
+                }
+            };
+            if done {
+                migration.steps.remove(0);
+            }
+        }
+        if migration.steps.is_empty() {
+            return true;
+        }
+        self.migration = Some(migration);
+        false
+    }
+
+    pub fn is_migrated(&self) -> bool {
+        self.migration.is_none()
+    }
+}
+
+/// Reads a collection handle back with another value type. Handles only hold their
+/// storage prefix and size, so both read and write the same entries.
+fn retype<A: BorshSerialize, B: BorshDeserialize>(handle: &A) -> B {
+    B::try_from_slice(&handle.try_to_vec().unwrap()).unwrap()
+}
+
+/// Position of the project in the `UnorderedSet` of its round, which keeps positions
+/// under its prefix followed by `i`.
+fn project_index(project_id: &ProjectId) -> u32 {
+    let prefix = StorageKey::ProjectsPerRound { round_id: project_id.0 }.into_storage_key();
+    let indices: LookupMap<ProjectId, u64> = LookupMap::new([prefix, vec![b'i']].concat());
+    indices.get(project_id).expect("ERR_PROJECT_NOT_FOUND") as u32
+}
+
+/// Raw value of `key` in the `TreeMap` under `prefix`.
+fn read_value<K: BorshSerialize>(prefix: StorageKey, key: &K) -> Vec<u8> {
+    let storage_key = [prefix.into_storage_key(), vec![b'v'], key.try_to_vec().unwrap()].concat();
+    env::storage_read(&storage_key).unwrap()
+}
+
+/// Tags the value of `key` in the `TreeMap` under `prefix` without reading the old one,
+/// which has another layout. `TreeMap` keeps values under its prefix followed by `v`.
+fn overwrite_value<K: BorshSerialize, V: BorshSerialize>(prefix: StorageKey, key: &K, value: &V) {
+    let mut values: LookupMap<K, V> =
+        LookupMap::new([prefix.into_storage_key(), vec![b'v']].concat());
+    values.insert_raw(&key.try_to_vec().unwrap(), &value.try_to_vec().unwrap());
+}
+
+impl Contract {
+    pub(crate) fn assert_migrated(&self) {
+        require!(self.migration.is_none(), "ERR_MIGRATION_PENDING");
+    }
+
+    /// `sudo_migrate` then tags rounds and projects as `V1`, they are upgraded lazily,
+    /// moves votes and grants to per project records, indexes voters of every project
+    /// from them and ranks projects. Rounds still open are put on the calendar last,
+    /// the current round of the first release first.
+    fn migrate_v1(old: ContractV1) -> Self {
+        let ContractV1 {
+            owner_id,
+            operators,
+            projects,
+            rounds_for_owner,
+            grants,
+            votes,
+            round_projects,
+            rounds,
+            current_round_id,
+            fee_point,
+            fee_amount,
+            default_duration,
+            default_vote_cost,
+        } = old;
+        Self {
+            treasury_id: owner_id.clone(),
+            owner_id,
+            operators,
+            projects: retype(&projects),
+            rounds_for_owner,
+            vote_records: LookupMap::new(StorageKey::VoteRecords),
+            round_votes: LookupMap::new(StorageKey::RoundVotes),
+            round_projects,
+            project_voters: UnorderedMap::new(StorageKey::ProjectVoters),
+            pair_weights: LookupMap::new(StorageKey::PairWeights),
+            storage_accounts: LookupMap::new(StorageKey::StorageAccounts),
+            rounds: retype(&rounds),
+            last_round_id: current_round_id,
+            fee_point,
+            fee_amount,
+            token_fee_amounts: UnorderedMap::new(StorageKey::TokenFeeAmounts),
+            default_duration,
+            default_vote_cost,
+            default_review_duration: DEFAULT_REVIEW_DURATION,
+            upgrade_delay: 0,
+            staged_upgrade: None,
+            project_revisions: LookupMap::new(StorageKey::ProjectRevisions),
+            reserve: U128(0),
+            token_reserves: UnorderedMap::new(StorageKey::TokenReserves),
+            donations: LookupMap::new(StorageKey::RoundDonations),
+            token_donations: LookupMap::new(StorageKey::RoundTokenDonations),
+            schedule: TreeMap::new(StorageKey::Schedule),
+            challenges: LookupMap::new(StorageKey::Challenges),
+            project_rankings: LookupMap::new(StorageKey::ProjectRankings),
+            round_results: LookupMap::new(StorageKey::RoundResults),
+            ranked_results: LookupMap::new(StorageKey::RankedResults),
+            migration: Some(Migration {
+                steps: vec![
+                    MigrationStep::Rounds { cursor: None },
+                    MigrationStep::Projects { cursor: None },
+                    MigrationStep::Votes,
+                    MigrationStep::Schedule { first: Some(current_round_id), cursor: None },
+                ],
+                votes,
+                grants,
+            }),
+        }
+    }
+
+    /// Tags rounds of the first release as `V1`, returns true when all are.
+    fn internal_migrate_rounds(
+        &mut self,
+        migration: &mut Migration,
+        cursor: Option<RoundId>,
+        limit: &mut u32,
+    ) -> bool {
+        let mut cursor = cursor;
+        while *limit > 0 {
+            let next = match cursor {
+                Some(cursor) => self.rounds.higher(&cursor),
+                None => self.rounds.min(),
+            };
+            let round_id = match next {
+                Some(round_id) => round_id,
+                None => return true,
+            };
+            // Values which do not read as the first release layout are already versioned.
+            let raw = read_value(StorageKey::Rounds, &round_id);
+            if let Ok(round) = RoundV1::try_from_slice(&raw) {
+                overwrite_value(StorageKey::Rounds, &round_id, &VersionedRound::V1(round));
+            }
+            cursor = Some(round_id);
+            *limit -= 1;
+        }
+        if cursor == self.rounds.max() {
+            return true;
+        }
+        migration.steps[0] = MigrationStep::Rounds { cursor };
+        false
+    }
+
+    /// Tags projects of the first release as `V1`, opens their voter index and
+    /// ranks them, returns true when all are.
+    fn internal_migrate_projects(
+        &mut self,
+        migration: &mut Migration,
+        cursor: Option<ProjectId>,
+        limit: &mut u32,
+    ) -> bool {
+        let mut cursor = cursor;
+        while *limit > 0 {
+            let next = match &cursor {
+                Some(cursor) => self.projects.higher(cursor),
+                None => self.projects.min(),
+            };
+            let project_id = match next {
+                Some(project_id) => project_id,
+                None => return true,
+            };
+            let raw = read_value(StorageKey::Projects, &project_id);
+            if let Ok(project) = ProjectV1::try_from_slice(&raw) {
+                overwrite_value(StorageKey::Projects, &project_id, &VersionedProject::V1(project));
+                self.project_voters.insert(
+                    &project_id,
+                    &UnorderedMap::new(StorageKey::VotersPerProject {
+                        project_id: project_id.clone(),
+                    }),
+                );
+                let mut project = self.internal_get_project(&project_id).unwrap();
+                project.index = project_index(&project_id);
+                self.internal_save_project(&project_id, &project);
+                self.internal_rank_project(None, &project);
+                // Rounds finished by the first release have no results to add up.
+                let mut round = self.get_round(project.round_id).expect("ERR_ROUND_NOT_FOUND");
+                if round.status == RoundStatus::Finished {
+                    round.add_matched(&None, round.matched_of(&project, &None));
+                    self.internal_save_round(&round);
+                }
+            }
+            cursor = Some(project_id);
+            *limit -= 1;
+        }
+        if cursor == self.projects.max() {
+            return true;
+        }
+        migration.steps[0] = MigrationStep::Projects { cursor };
+        false
+    }
+
+    /// Moves votes and grants of one voter at a time to vote records and indexes
+    /// the voters of projects.
+    fn internal_migrate_votes(&mut self, migration: &mut Migration, limit: &mut u32) -> bool {
+        while *limit > 0 {
+            let voter = match [migration.votes.keys_as_vector(), migration.grants.keys_as_vector()]
+                .iter()
+                .find_map(|keys| keys.len().checked_sub(1).and_then(|last| keys.get(last)))
+            {
+                Some(voter) => voter,
+                None => return true,
+            };
+            let votes = migration.votes.remove(&voter).unwrap_or_default();
+            let grants = migration.grants.remove(&voter).unwrap_or_default();
+            let mut records: HashMap<ProjectId, VoteRecord> = HashMap::new();
+            for (project_id, voted) in votes {
+                records.entry(project_id).or_default().votes = voted;
+            }
+            for (project_id, amount) in grants {
+                records.entry(project_id).or_default().grants = amount;
+            }
+            for (project_id, record) in records {
+                if record.votes > 0 {
+                    let mut voters: UnorderedMap<AccountId, u64> =
+                        self.project_voters.get(&project_id).expect("ERR_PROJECT_NOT_FOUND");
+                    voters.insert(&voter, &record.votes);
+                    self.project_voters.insert(&project_id, &voters);
+                }
+                self.internal_save_vote(&voter, &project_id, &record);
+            }
+            *limit -= 1;
+        }
+        migration.votes.is_empty() && migration.grants.is_empty()
+    }
+
+    /// Puts rounds of the first release which are still open on the calendar,
+    /// `first` before the others. Returns true when all rounds are checked.
+    fn internal_migrate_schedule(
+        &mut self,
+        migration: &mut Migration,
+        first: Option<RoundId>,
+        cursor: Option<RoundId>,
+        limit: &mut u32,
+    ) -> bool {
+        let mut round_ids: Vec<RoundId> = first.into_iter().collect();
+        // Round ids start from 1.
+        let rest: Vec<RoundId> = self
+            .rounds
+            .iter_from(cursor.unwrap_or(0))
+            .map(|(round_id, _)| round_id)
+            .take(*limit as usize - round_ids.len())
+            .collect();
+        let cursor = rest.last().cloned().or(cursor);
+        round_ids.extend(rest);
+        let now = (env::block_timestamp_ms() / 1_000) as u32;
+        for round_id in round_ids {
+            *limit -= 1;
+            let round = match self.get_round(round_id) {
+                Some(round) => round,
+                None => continue,
+            };
+            if round.status == RoundStatus::Active
+                && round.end_at >= now
+                && self.internal_is_slot_free(&round)
+            {
+                self.schedule.insert(&round.start_at, &round.id);
+            }
+        }
+        if *limit == 0 && cursor != self.rounds.max() {
+            migration.steps[0] = MigrationStep::Schedule { first: None, cursor };
+            return false;
+        }
+        true
+    }
+}
@@ -1,5 +1,21 @@
 use crate::*;
-use near_sdk::{assert_one_yocto, near_bindgen};
+use near_sdk::json_types::{Base58CryptoHash, Base64VecU8};
+use near_sdk::{assert_one_yocto, near_bindgen, require, CryptoHash, GasWeight, Promise};
+use serde::{Deserialize, Serialize};
+use std::convert::TryInto;
+
+/// Storage key of the staged wasm, kept out of the contract state.
+const STAGED_CODE_KEY: &[u8] = b"STAGED_CODE";
+
+/// Code staged by `stage_upgrade`.
+#[witgen::witgen]
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone)]
+pub struct StagedUpgrade {
+    pub code_hash: Base58CryptoHash,
+    pub staged_at: u32,
+    /// Time after which `deploy_upgrade` can run.
+    pub deployable_at: u32,
+}
 
 impl Contract {
     pub fn assert_owner(&self) {
@@ -23,6 +39,16 @@ impl Contract {
         assert_one_yocto();
         self.assert_owner();
         self.owner_id = new_owner_id;
+        self.emit_config_changed();
+    }
+
+    /// Set account that receives platform fees. Only can be called by owner.
+    #[payable]
+    pub fn set_treasury(&mut self, treasury_id: AccountId) {
+        assert_one_yocto();
+        self.assert_owner();
+        self.treasury_id = treasury_id;
+        self.emit_config_changed();
     }
 
     /// Get operators
@@ -33,16 +59,76 @@ impl Contract {
     /// Extend operators. Only can be called by owner.
     pub fn extend_operators(&mut self, operators: Vec<AccountId>) {
         self.assert_owner();
-        for operator in operators {
-            self.operators.insert(&operator);
+        for operator in &operators {
+            self.operators.insert(operator);
         }
+        Event::OperatorsAdded { operators: &operators }.emit();
     }
 
     /// Remove operators. Only can be called by owner.
     pub fn remove_operators(&mut self, operators: Vec<AccountId>) {
         self.assert_owner();
-        for operator in operators {
-            self.operators.remove(&operator);
+        for operator in &operators {
+            self.operators.remove(operator);
         }
+        Event::OperatorsRemoved { operators: &operators }.emit();
+    }
+
+    /// Set seconds between staging code and deploying it. Only can be called by owner.
+    /// The delay only can go up, lowering it takes an upgrade which waits the current delay.
+    #[payable]
+    pub fn set_upgrade_delay(&mut self, upgrade_delay: u32) {
+        assert_one_yocto();
+        self.assert_owner();
+        require!(upgrade_delay >= self.upgrade_delay, "ERR_UPGRADE_DELAY_DECREASE");
+        self.upgrade_delay = upgrade_delay;
+        self.emit_config_changed();
+    }
+
+    /// Store new contract code to be deployed after the upgrade delay,
+    /// replacing code staged before. Only can be called by owner.
+    #[payable]
+    pub fn stage_upgrade(&mut self, code: Base64VecU8) -> StagedUpgrade {
+        assert_one_yocto();
+        self.assert_owner();
+        let code: Vec<u8> = code.into();
+        require!(!code.is_empty(), "ERR_EMPTY_CODE");
+        let code_hash: CryptoHash = env::sha256(&code).try_into().unwrap();
+        env::storage_write(STAGED_CODE_KEY, &code);
+        let staged_at = (env::block_timestamp_ms() / 1_000) as u32;
+        let staged = StagedUpgrade {
+            code_hash: code_hash.into(),
+            staged_at,
+            deployable_at: staged_at + self.upgrade_delay,
+        };
+        self.staged_upgrade = Some(staged.clone());
+        Event::UpgradeStaged { upgrade: &staged }.emit();
+        staged
+    }
+
+    /// Deploy staged code and call `migrate` on it. Only can be called by owner.
+    #[payable]
+    pub fn deploy_upgrade(&mut self) -> Promise {
+        assert_one_yocto();
+        self.assert_owner();
+        let staged = self.staged_upgrade.take().expect("ERR_NO_STAGED_UPGRADE");
+        require!(
+            staged.deployable_at <= (env::block_timestamp_ms() / 1_000) as u32,
+            "ERR_UPGRADE_DELAYED"
+        );
+        let code = env::storage_read(STAGED_CODE_KEY).expect("ERR_NO_STAGED_UPGRADE");
+        env::storage_remove(STAGED_CODE_KEY);
+        Event::UpgradeDeployed { upgrade: &staged }.emit();
+        Promise::new(env::current_account_id()).deploy_contract(code).function_call_weight(
+            "migrate".to_string(),
+            vec![],
+            0,
+            GAS_FOR_MIGRATE,
+            GasWeight(1),
+        )
+    }
+
+    pub fn get_staged_upgrade(&self) -> Option<StagedUpgrade> {
+        self.staged_upgrade.clone()
     }
 }
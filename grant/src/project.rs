@@ -1,16 +1,17 @@
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 
 use near_sdk::collections::UnorderedSet;
-use near_sdk::{env, json_types::U128, near_bindgen, AccountId, PanicOnDefault, PromiseOrValue};
-use near_sdk::{require, Promise};
+use near_sdk::PromiseOrValue;
+use near_sdk::{assert_one_yocto, is_promise_success, require, Promise};
+use near_sdk::{env, json_types::U128, near_bindgen, AccountId, Balance, PanicOnDefault};
 use serde::{Deserialize, Serialize};
-use witgen;
 
 use crate::*;
+use near_contract_standards::fungible_token::core::ext_ft_core;
 
 #[witgen::witgen]
 #[near_bindgen]
-#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, PanicOnDefault)]
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, PanicOnDefault, Clone)]
 pub struct Project {
     pub name: String,
     pub description: String,
@@ -23,12 +24,87 @@ pub struct Project {
     pub grants: U128,
     pub support_area: u64,
     pub withdrawn: U128,
+    pub beneficiary: Option<AccountId>,
+    pub delegates: Vec<AccountId>,
+    pub token_grants: HashMap<AccountId, U128>,
+    pub token_withdrawn: HashMap<AccountId, U128>,
+    pub match_area: U128,
+    /// Sums of scaled `√c` and of their squares, kept for `Clr` rounds.
+    pub sqrt_sum: U128,
+    pub square_sum: U128,
+    pub status: ProjectStatus,
+    /// Reason given by the operator who reviewed the project.
+    pub review_reason: Option<String>,
+    /// Position among projects of the round, which `sudo_tally_round` goes through in order.
+    pub index: u32,
+}
+
+/// Review state of a project application, only approved projects take votes.
+#[witgen::witgen]
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, PartialEq, Debug, Clone)]
+pub enum ProjectStatus {
+    Pending,
+    Approved,
+    Rejected,
+    /// Removed from matching after a challenge, voters can claim their grants back.
+    Disqualified,
 }
 
 #[witgen::witgen]
 pub type ProjectId = (RoundId, AccountId);
 
-impl Project {}
+/// Number of past metadata versions kept per project.
+pub const MAX_PROJECT_REVISIONS: usize = 10;
+
+/// Project metadata replaced by `update_project`.
+#[witgen::witgen]
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+pub struct ProjectRevision {
+    pub name: String,
+    pub description: String,
+    pub external_url: String,
+    pub image: String,
+    pub replaced_at: u32,
+}
+
+impl Project {
+    pub fn assert_owner(&self) {
+        require!(env::predecessor_account_id() == self.owner, "ERR_NOT_PROJECT_OWNER");
+    }
+
+    pub fn can_withdraw(&self, account_id: &AccountId) -> bool {
+        &self.owner == account_id || self.delegates.contains(account_id)
+    }
+
+    /// Account that receives payouts.
+    pub fn payout_account(&self) -> AccountId {
+        self.beneficiary.clone().unwrap_or_else(|| self.owner.clone())
+    }
+
+    /// Direct grants in NEAR or in `token_id`.
+    pub fn grants_of(&self, token_id: &Option<AccountId>) -> u128 {
+        match token_id {
+            None => self.grants.0,
+            Some(token_id) => self.token_grants.get(token_id).map_or(0, |amount| amount.0),
+        }
+    }
+
+    pub fn withdrawn_of(&self, token_id: &Option<AccountId>) -> u128 {
+        match token_id {
+            None => self.withdrawn.0,
+            Some(token_id) => self.token_withdrawn.get(token_id).map_or(0, |amount| amount.0),
+        }
+    }
+
+    pub fn set_withdrawn(&mut self, token_id: &Option<AccountId>, amount: u128) {
+        match token_id {
+            None => self.withdrawn = U128(amount),
+            Some(token_id) => {
+                self.token_withdrawn.insert(token_id.clone(), U128(amount));
+            }
+        }
+    }
+}
 
 impl Contract {
     pub fn is_project_exists(&self, project_id: &ProjectId) -> bool {
@@ -38,93 +114,291 @@ impl Contract {
     pub fn assert_unique_project(&self, project_id: &ProjectId) {
         require!(!self.is_project_exists(project_id), "ERR_PROJECT_EXISTS_IN_ROUND");
     }
+
+    pub(crate) fn internal_get_project(&self, project_id: &ProjectId) -> Option<Project> {
+        self.projects.get(project_id).map(Project::from)
+    }
+
+    pub(crate) fn internal_save_project(&mut self, project_id: &ProjectId, project: &Project) {
+        let old = self.internal_get_project(project_id);
+        self.internal_rank_project(old.as_ref(), project);
+        self.projects.insert(project_id, &VersionedProject::Current(project.clone()));
+    }
 }
 
 #[near_bindgen]
 impl Contract {
+    /// Applies to the round, the scheduled one taking applications if not given,
+    /// the project waits for review.
     pub fn new_project(
         &mut self,
+        round_id: Option<RoundId>,
         name: String,
         description: String,
         external_url: String,
         image: String,
     ) -> Project {
-        let project_id: ProjectId = (self.current_round_id, env::predecessor_account_id());
+        self.assert_migrated();
+        let storage_used = env::storage_usage();
+        let round_id = self.internal_application_round_id(round_id);
+        let project_id: ProjectId = (round_id, env::predecessor_account_id());
         self.assert_unique_project(&project_id);
-        let mut round: Round = self.get_current_round().expect("Expected available round");
+        let round: Round = self.get_round(round_id).expect("ERR_ROUND_NOT_FOUND");
+        require!(round.is_accepting_projects(), "ERR_NOT_APPLICATION_PHASE");
+        let mut projects = self.round_projects.get(&round_id).expect("ERR_ROUND_NOT_FOUND");
         let project = Project {
             name,
             description,
             external_url,
             image,
-            round_id: self.current_round_id,
+            round_id,
             owner: env::predecessor_account_id(),
             created_at: (env::block_timestamp_ms() / 1_000) as u32,
             total_votes: 0,
             grants: U128(0),
             support_area: 0,
             withdrawn: 0.into(),
+            beneficiary: None,
+            delegates: vec![],
+            token_grants: HashMap::new(),
+            token_withdrawn: HashMap::new(),
+            match_area: U128(0),
+            sqrt_sum: U128(0),
+            square_sum: U128(0),
+            status: ProjectStatus::Pending,
+            review_reason: None,
+            index: projects.len() as u32,
         };
-        round.projects += 1;
-        self.rounds.insert(&self.current_round_id, &round);
-        self.projects.insert(&project_id, &project);
-        {
-            let mut rounds =
-                self.round_projects.get(&self.current_round_id).expect("ERR_ROUND_NOT_FOUND");
-            rounds.insert(&project_id);
-            self.round_projects.insert(&self.current_round_id, &rounds);
-        }
+        self.internal_save_project(&project_id, &project);
+        self.project_voters.insert(
+            &project_id,
+            &UnorderedMap::new(StorageKey::VotersPerProject { project_id: project_id.clone() }),
+        );
+        projects.insert(&project_id);
+        self.round_projects.insert(&round_id, &projects);
         {
             let mut rounds = self.rounds_for_owner.get(&env::predecessor_account_id()).unwrap_or(
                 UnorderedSet::new(StorageKey::AccountRounds {
                     account_id: env::predecessor_account_id(),
                 }),
             );
-            rounds.insert(&self.current_round_id);
+            rounds.insert(&round_id);
             self.rounds_for_owner.insert(&env::predecessor_account_id(), &rounds);
         }
+        let storage_cost =
+            self.internal_charge_storage(&env::predecessor_account_id(), storage_used);
+        Event::ProjectCreated {
+            project_id: &project_id,
+            project: &project,
+            storage_cost: U128(storage_cost),
+        }
+        .emit();
         project
     }
 
-    pub fn grant_for(&self, project_id: ProjectId) -> (U128, U128) {
-        let project = self.projects.get(&project_id).expect("ERR_PROJECT_NOT_FOUND");
+    /// Returns withdrawable and total granted amounts in NEAR or in `token_id`.
+    pub fn grant_for(&self, project_id: ProjectId, token_id: Option<AccountId>) -> (U128, U128) {
+        let project = self.internal_get_project(&project_id).expect("ERR_PROJECT_NOT_FOUND");
         let round = self.get_round(project.round_id).expect("ERR_ROUND_NOT_FOUND");
-        if round.id == self.current_round_id && round.is_active() {
+        if round.is_active()
+            || round.status == RoundStatus::Cancelled
+            || project.status == ProjectStatus::Disqualified
+        {
             (U128(0), U128(0))
         } else {
-            let mut granted = project.grants.0;
-            if round.support_area > 0 {
-                granted += project.support_area as u128 * round.support_pool.0
-                    / round.support_area as u128;
-            }
-            (U128(granted - project.withdrawn.0), U128(granted))
+            let granted = match self.round_results.get(&project_id) {
+                Some(result) => result.grants_of(&token_id) + result.matched_of(&token_id),
+                None => project.grants_of(&token_id) + round.matched_of(&project, &token_id),
+            };
+            (U128(granted - project.withdrawn_of(&token_id)), U128(granted))
         }
     }
 
-    pub fn withdraw(&mut self, project_id: ProjectId, amount: U128) -> PromiseOrValue<U128> {
-        let mut project = self.projects.get(&project_id).expect("ERR_PROJECT_NOT_FOUND");
-        self.get_round(project.round_id).expect("ERR_ROUND_NOT_FOUND");
-        let (withdrawable, _) = self.grant_for(project_id.clone());
+    /// Withdraw NEAR, or `token_id` if given, to the project payout account.
+    pub fn withdraw(
+        &mut self,
+        project_id: ProjectId,
+        amount: U128,
+        token_id: Option<AccountId>,
+    ) -> PromiseOrValue<U128> {
+        self.assert_migrated();
+        let mut project = self.internal_get_project(&project_id).expect("ERR_PROJECT_NOT_FOUND");
+        require!(project.can_withdraw(&env::predecessor_account_id()), "ERR_NOT_ALLOWED");
+        let round = self.get_round(project.round_id).expect("ERR_ROUND_NOT_FOUND");
+        require!(round.status == RoundStatus::Finished, "ERR_ROUND_NOT_FINISHED");
+        let (withdrawable, _) = self.grant_for(project_id.clone(), token_id.clone());
         require!(amount.0 <= withdrawable.0, "ERR_TOO_MUCH");
-        project.withdrawn = U128(project.withdrawn.0 + amount.0);
-        self.projects.insert(&project_id.clone(), &project);
-        Promise::new(project.owner).transfer(amount.0).into()
+        project.set_withdrawn(&token_id, project.withdrawn_of(&token_id) + amount.0);
+        self.internal_save_project(&project_id, &project);
+        let receiver_id = project.payout_account();
+        let transfer = match &token_id {
+            None => Promise::new(receiver_id.clone()).transfer(amount.0),
+            Some(token_id) => ext_ft_core::ext(token_id.clone())
+                .with_attached_deposit(1)
+                .with_static_gas(GAS_FOR_FT_TRANSFER)
+                .ft_transfer(receiver_id.clone(), amount, None),
+        };
+        transfer
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_RESOLVE_TRANSFER)
+                    .on_withdraw(project_id, receiver_id, amount, token_id),
+            )
+            .into()
+    }
+
+    /// Resolves `withdraw`: on failed transfer gives `amount` back to the project.
+    /// Returns the amount actually paid out.
+    #[private]
+    pub fn on_withdraw(
+        &mut self,
+        project_id: ProjectId,
+        receiver_id: AccountId,
+        amount: U128,
+        token_id: Option<AccountId>,
+    ) -> U128 {
+        if is_promise_success() {
+            Event::Withdraw {
+                project_id: &project_id,
+                receiver_id: &receiver_id,
+                amount,
+                token_id: token_id.as_ref(),
+            }
+            .emit();
+            return amount;
+        }
+        let mut project = self.internal_get_project(&project_id).expect("ERR_PROJECT_NOT_FOUND");
+        project.set_withdrawn(&token_id, project.withdrawn_of(&token_id) - amount.0);
+        self.internal_save_project(&project_id, &project);
+        U128(0)
+    }
+
+    /// Set account that receives payouts, `None` pays to the project owner.
+    /// Only can be called by project owner.
+    #[payable]
+    pub fn set_project_beneficiary(
+        &mut self,
+        project_id: ProjectId,
+        beneficiary: Option<AccountId>,
+    ) -> Project {
+        assert_one_yocto();
+        self.assert_migrated();
+        let mut project = self.internal_get_project(&project_id).expect("ERR_PROJECT_NOT_FOUND");
+        project.assert_owner();
+        project.beneficiary = beneficiary;
+        let storage_used = env::storage_usage();
+        self.internal_save_project(&project_id, &project);
+        self.internal_charge_storage(&project.owner, storage_used);
+        Event::ProjectUpdated { project_id: &project_id, project: &project }.emit();
+        project
+    }
+
+    /// Set accounts allowed to call `withdraw` besides the owner.
+    /// Only can be called by project owner.
+    #[payable]
+    pub fn set_project_delegates(
+        &mut self,
+        project_id: ProjectId,
+        delegates: Vec<AccountId>,
+    ) -> Project {
+        assert_one_yocto();
+        self.assert_migrated();
+        let mut project = self.internal_get_project(&project_id).expect("ERR_PROJECT_NOT_FOUND");
+        project.assert_owner();
+        project.delegates = delegates;
+        let storage_used = env::storage_usage();
+        self.internal_save_project(&project_id, &project);
+        self.internal_charge_storage(&project.owner, storage_used);
+        Event::ProjectUpdated { project_id: &project_id, project: &project }.emit();
+        project
+    }
+
+    /// Approve a pending project, it takes votes and gets matched from now on.
+    /// Only can be called by owner or operators while the round is open.
+    pub fn sudo_approve_project(
+        &mut self,
+        project_id: ProjectId,
+        reason: Option<String>,
+    ) -> Project {
+        self.internal_review_project(project_id, ProjectStatus::Approved, reason)
+    }
+
+    /// Reject a pending project. Only can be called by owner or operators.
+    pub fn sudo_reject_project(&mut self, project_id: ProjectId, reason: String) -> Project {
+        self.internal_review_project(project_id, ProjectStatus::Rejected, Some(reason))
+    }
+
+    /// Update project metadata while its round is open, previous values are kept
+    /// in the revision history. Only can be called by project owner.
+    #[payable]
+    pub fn update_project(
+        &mut self,
+        project_id: ProjectId,
+        name: Option<String>,
+        description: Option<String>,
+        external_url: Option<String>,
+        image: Option<String>,
+    ) -> Project {
+        assert_one_yocto();
+        self.assert_migrated();
+        let mut project = self.internal_get_project(&project_id).expect("ERR_PROJECT_NOT_FOUND");
+        project.assert_owner();
+        let round = self.get_round(project.round_id).expect("ERR_ROUND_NOT_FOUND");
+        let now = (env::block_timestamp_ms() / 1_000) as u32;
+        require!(
+            round.status == RoundStatus::Active && now <= round.end_at,
+            "ERR_ROUND_NOT_ACTIVE"
+        );
+        let storage_used = env::storage_usage();
+        let mut revisions = self.project_revisions.get(&project_id).unwrap_or_default();
+        if revisions.len() == MAX_PROJECT_REVISIONS {
+            revisions.remove(0);
+        }
+        revisions.push(ProjectRevision {
+            name: project.name.clone(),
+            description: project.description.clone(),
+            external_url: project.external_url.clone(),
+            image: project.image.clone(),
+            replaced_at: now,
+        });
+        self.project_revisions.insert(&project_id, &revisions);
+        update_if_some!(project, name);
+        update_if_some!(project, description);
+        update_if_some!(project, external_url);
+        update_if_some!(project, image);
+        self.internal_save_project(&project_id, &project);
+        self.internal_charge_storage(&project.owner, storage_used);
+        Event::ProjectUpdated { project_id: &project_id, project: &project }.emit();
+        project
+    }
+
+    /// Past metadata of the project, oldest first.
+    pub fn get_project_revisions(&self, project_id: ProjectId) -> Vec<ProjectRevision> {
+        self.project_revisions.get(&project_id).unwrap_or_default()
     }
 
     pub fn get_project(&self, project_id: ProjectId) -> Option<Project> {
-        self.projects.get(&project_id)
+        self.internal_get_project(&project_id)
     }
 
-    pub fn list_projects(&self, limit: Option<u32>, offset: Option<u32>) -> Vec<Project> {
+    /// Projects of the round, only ones with `status` if given.
+    pub fn list_projects(
+        &self,
+        round_id: RoundId,
+        limit: Option<u32>,
+        offset: Option<u32>,
+        status: Option<ProjectStatus>,
+    ) -> Vec<Project> {
         let limit = limit.unwrap_or(u32::MAX);
         let offset = offset.unwrap_or(0);
         self.round_projects
-            .get(&self.current_round_id)
-            .unwrap()
+            .get(&round_id)
+            .expect("ERR_ROUND_NOT_FOUND")
             .iter()
+            .map(|project_id| self.internal_get_project(&project_id).unwrap())
+            .filter(|project| status.as_ref().is_none_or(|status| &project.status == status))
             .skip(offset as usize)
             .take(limit as usize)
-            .map(|project_id| self.projects.get(&project_id).unwrap())
             .collect()
     }
 
@@ -141,7 +415,7 @@ impl Contract {
                 .iter()
                 .skip(offset as usize)
                 .take(limit as usize)
-                .map(|round_id| self.projects.get(&(round_id, owner_id.clone())).unwrap())
+                .map(|round_id| self.internal_get_project(&(round_id, owner_id.clone())).unwrap())
                 .collect(),
             _ => {
                 vec![]
@@ -149,52 +423,175 @@ impl Contract {
         }
     }
 
+    /// Vote for the project paying with attached NEAR, the excess is refunded.
+    /// When the round has a registry, the vote is applied once the registry confirms
+    /// that the voter is eligible and resolves to `null` with a full refund otherwise.
     #[payable]
-    pub fn vote(&mut self, project_id: ProjectId, votes: u64) -> Project {
+    pub fn vote(&mut self, project_id: ProjectId, votes: u64) -> PromiseOrValue<Project> {
+        self.assert_migrated();
         let voter = env::predecessor_account_id();
-        let storage_used = env::storage_usage();
-        let mut round: Round = self.get_round(project_id.0).expect("ERR_ROUND_NOT_FOUND");
-        require!(round.is_active(), "ERR_ROUND_NOT_ACTIVE");
-        require!(project_id.0 == self.current_round_id, "ERR_ROUND_WRONG");
+        let deposit = env::attached_deposit();
+        let round: Round = self.get_round(project_id.0).expect("ERR_ROUND_NOT_FOUND");
+        match round.registry_id.clone() {
+            Some(registry_id) => {
+                let error = self.internal_vote_error(&voter, &project_id, votes, deposit);
+                require!(error.is_none(), error.unwrap_or_default());
+                let pairs_gas = self.internal_pairs_to_update(&round, &project_id).0;
+                ext_registry::ext(registry_id)
+                    .with_static_gas(GAS_FOR_IS_ELIGIBLE)
+                    .is_eligible(voter.clone())
+                    .then(
+                        Self::ext(env::current_account_id())
+                            .with_static_gas(GAS_FOR_RESOLVE_VOTE + pairs_gas)
+                            .on_eligible_vote(voter, project_id, votes, U128(deposit)),
+                    )
+                    .into()
+            }
+            None => {
+                PromiseOrValue::Value(self.internal_near_vote(voter, project_id, votes, deposit))
+            }
+        }
+    }
+}
 
-        let mut project = self.projects.get(&project_id).expect("ERR_PROJECT_NOT_FOUND");
+/// Weight paid for `votes` more votes on top of `voted` ones, the n-th vote weighs n.
+fn vote_weight(voted: u64, votes: u64) -> u64 {
+    votes * (votes + 1) / 2 + votes * voted
+}
 
-        let mut user_votes = self.votes.get(&voter).unwrap_or(HashMap::new());
-        let voted = *user_votes.get(&project_id).unwrap_or(&0);
+impl Contract {
+    /// Weight `voter` pays for `votes` more votes for the project.
+    pub(crate) fn internal_vote_weight(
+        &self,
+        voter: &AccountId,
+        project_id: &ProjectId,
+        votes: u64,
+    ) -> u64 {
+        vote_weight(self.internal_get_vote(voter, project_id).votes, votes)
+    }
 
-        let mut user_grants = self.grants.get(&voter).unwrap_or(HashMap::new());
-        let granted = *user_grants.get(&project_id).unwrap_or(&U128(0));
+    /// Applies a vote paid with `deposit` in NEAR and refunds the excess.
+    pub(crate) fn internal_near_vote(
+        &mut self,
+        voter: AccountId,
+        project_id: ProjectId,
+        votes: u64,
+        deposit: Balance,
+    ) -> Project {
+        let storage_used = env::storage_usage();
+        let (mut project, weight) = self.internal_vote(&voter, &project_id, votes);
+        let mut round: Round = self.get_round(project_id.0).expect("ERR_ROUND_NOT_FOUND");
 
-        let weight = votes * (votes + 1) / 2 + votes * voted;
         let cost = weight as u128 * round.vote_cost.0;
-        let platform_fee = cost * self.fee_point as u128 / 10000;
+        let platform_fee = cost * round.fee_point(self.fee_point) as u128 / 10000;
         let grants = cost - platform_fee;
-        let support_area = votes * (project.total_votes - voted);
-
-        self.fee_amount = U128(self.fee_amount.0 + platform_fee);
 
-        user_votes.insert(project_id.clone(), voted + votes);
-        self.votes.insert(&voter, &user_votes);
+        round.add_fee(&None, platform_fee);
+        self.internal_save_round(&round);
 
-        user_grants.insert(project_id.clone(), U128(granted.0 + grants));
-        self.grants.insert(&voter, &user_grants);
+        self.internal_add_grant(&voter, &project_id, &None, grants, platform_fee);
 
-        project.total_votes += votes;
-        project.support_area += support_area;
         project.grants = U128(project.grants.0 + grants);
-        self.projects.insert(&project_id, &project);
+        self.internal_save_project(&project_id, &project);
 
-        round.support_area += support_area;
-        self.rounds.insert(&project_id.0, &round);
+        let storage_cost = self.internal_charge_storage(&voter, storage_used);
 
-        let storage_cost =
-            (env::storage_usage() - storage_used) as u128 * env::STORAGE_PRICE_PER_BYTE;
+        require!(cost <= deposit, "ERR_NOT_ENOUGH");
 
-        require!(cost + storage_cost <= env::attached_deposit(), "ERR_NOT_ENOUGH");
+        let refund = deposit - cost;
+        Event::Vote {
+            voter_id: &voter,
+            project_id: &project_id,
+            votes,
+            cost: U128(cost),
+            platform_fee: U128(platform_fee),
+            storage_cost: U128(storage_cost),
+            refund: U128(refund),
+            token_id: None,
+        }
+        .emit();
+        if refund > 0 {
+            Promise::new(voter).transfer(refund);
+        }
+        project
+    }
 
-        if cost + storage_cost < env::attached_deposit() {
-            Promise::new(voter).transfer(env::attached_deposit() - cost - storage_cost);
+    fn internal_review_project(
+        &mut self,
+        project_id: ProjectId,
+        status: ProjectStatus,
+        reason: Option<String>,
+    ) -> Project {
+        self.assert_owner_or_operator();
+        self.assert_migrated();
+        let mut project = self.internal_get_project(&project_id).expect("ERR_PROJECT_NOT_FOUND");
+        require!(project.status == ProjectStatus::Pending, "ERR_PROJECT_REVIEWED");
+        let mut round = self.get_round(project.round_id).expect("ERR_ROUND_NOT_FOUND");
+        if status == ProjectStatus::Approved {
+            let now = (env::block_timestamp_ms() / 1_000) as u32;
+            require!(
+                round.status == RoundStatus::Active && now <= round.end_at,
+                "ERR_ROUND_NOT_ACTIVE"
+            );
+            require!(
+                round.params.max_projects.is_none_or(|max_projects| round.projects < max_projects),
+                "ERR_TOO_MANY_PROJECTS"
+            );
+            round.projects += 1;
+            self.internal_save_round(&round);
         }
+        project.status = status;
+        project.review_reason = reason;
+        self.internal_save_project(&project_id, &project);
+        Event::ProjectReviewed {
+            project_id: &project_id,
+            status: &project.status,
+            reason: project.review_reason.as_ref(),
+        }
+        .emit();
         project
     }
+
+    /// Counts `votes` of `voter` in the project and its round.
+    /// Returns the project, which is left for the caller to save, and the weight to pay for.
+    pub(crate) fn internal_vote(
+        &mut self,
+        voter: &AccountId,
+        project_id: &ProjectId,
+        votes: u64,
+    ) -> (Project, u64) {
+        let mut round: Round = self.get_round(project_id.0).expect("ERR_ROUND_NOT_FOUND");
+        require!(round.is_active(), "ERR_ROUND_NOT_ACTIVE");
+
+        let mut project = self.internal_get_project(project_id).expect("ERR_PROJECT_NOT_FOUND");
+        require!(project.status == ProjectStatus::Approved, "ERR_PROJECT_NOT_APPROVED");
+
+        let mut record = self.internal_get_vote(voter, project_id);
+        let voted = record.votes;
+        if let Some(max_votes) = round.params.max_votes_per_voter {
+            let round_votes =
+                self.round_votes.get(&(voter.clone(), round.id)).map_or(0, |round| round.votes);
+            require!(round_votes + votes <= max_votes, "ERR_TOO_MANY_VOTES");
+        }
+        require!(self.internal_has_room_for(&round, project_id, voter), "ERR_TOO_MANY_VOTERS");
+
+        let weight = vote_weight(voted, votes);
+        let support_area = votes * (project.total_votes - voted);
+
+        record.votes += votes;
+        self.internal_save_vote(voter, project_id, &record);
+
+        self.internal_update_pair_weights(&round, project_id, voter, voted, voted + votes);
+        let mut voters = self.project_voters.get(project_id).expect("ERR_PROJECT_NOT_FOUND");
+        voters.insert(voter, &(voted + votes));
+        self.project_voters.insert(project_id, &voters);
+
+        project.total_votes += votes;
+        project.support_area += support_area;
+
+        round.support_area += support_area;
+        round.update_match_area(&mut project, voted, voted + votes);
+        self.internal_save_round(&round);
+        (project, weight)
+    }
 }
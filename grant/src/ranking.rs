@@ -0,0 +1,120 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::TreeMap;
+use near_sdk::json_types::U128;
+use near_sdk::{near_bindgen, AccountId};
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// Orders of `list_projects_v2`, projects with bigger values go first.
+#[witgen::witgen]
+#[derive(
+    Serialize, Deserialize, BorshDeserialize, BorshSerialize, PartialEq, Eq, Debug, Clone, Copy,
+)]
+pub enum ProjectSort {
+    TotalVotes,
+    Grants,
+    /// Share of the matching pool, by support area or match area depending on the strategy.
+    EstimatedMatch,
+    CreatedAt,
+}
+
+const PROJECT_SORTS: [ProjectSort; 4] = [
+    ProjectSort::TotalVotes,
+    ProjectSort::Grants,
+    ProjectSort::EstimatedMatch,
+    ProjectSort::CreatedAt,
+];
+
+/// Position of a project in a ranking, its sort value and owner.
+#[witgen::witgen]
+pub type ProjectCursor = (U128, AccountId);
+
+#[witgen::witgen]
+#[derive(Serialize, Deserialize)]
+pub struct ProjectPage {
+    pub projects: Vec<Project>,
+    /// Pass as `from_key` to get the next page, missing on the last page.
+    pub next_key: Option<ProjectCursor>,
+}
+
+impl Contract {
+    fn sort_value(round: &Round, project: &Project, sort: ProjectSort) -> u128 {
+        match sort {
+            ProjectSort::TotalVotes => project.total_votes as u128,
+            ProjectSort::Grants => project.grants.0,
+            ProjectSort::EstimatedMatch => match round.matching {
+                MatchingStrategy::Pairwise => project.support_area as u128,
+                _ => project.match_area.0,
+            },
+            ProjectSort::CreatedAt => project.created_at as u128,
+        }
+    }
+
+    pub(crate) fn internal_get_ranking(
+        &self,
+        round_id: RoundId,
+        sort: ProjectSort,
+    ) -> TreeMap<(u128, AccountId), ()> {
+        self.project_rankings
+            .get(&(round_id, sort))
+            .unwrap_or_else(|| TreeMap::new(StorageKey::ProjectRanking { round_id, sort }))
+    }
+
+    /// Moves the project in rankings of its round from values of `old` to values of `project`.
+    pub(crate) fn internal_rank_project(&mut self, old: Option<&Project>, project: &Project) {
+        let round = self.get_round(project.round_id).expect("ERR_ROUND_NOT_FOUND");
+        for sort in PROJECT_SORTS {
+            let value = Self::sort_value(&round, project, sort);
+            let old_value = old.map(|old| Self::sort_value(&round, old, sort));
+            if old_value == Some(value) {
+                continue;
+            }
+            let mut ranking = self.internal_get_ranking(round.id, sort);
+            if let Some(old_value) = old_value {
+                ranking.remove(&(old_value, project.owner.clone()));
+            }
+            ranking.insert(&(value, project.owner.clone()), &());
+            self.project_rankings.insert(&(round.id, sort), &ranking);
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Projects of the round ordered by `sort_by`, starting after `from_key`.
+    /// Every page costs the same whatever its depth.
+    pub fn list_projects_v2(
+        &self,
+        round_id: RoundId,
+        sort_by: ProjectSort,
+        from_key: Option<ProjectCursor>,
+        limit: Option<u32>,
+    ) -> ProjectPage {
+        self.get_round(round_id).expect("ERR_ROUND_NOT_FOUND");
+        let limit = limit.unwrap_or(u32::MAX) as usize;
+        let ranking = self.internal_get_ranking(round_id, sort_by);
+        let keys: Vec<(u128, AccountId)> = match from_key {
+            None => ranking.iter_rev().map(|(key, _)| key).take(limit.saturating_add(1)).collect(),
+            Some((value, owner)) => ranking
+                .iter_rev_from((value.0, owner))
+                .map(|(key, _)| key)
+                .take(limit.saturating_add(1))
+                .collect(),
+        };
+        let next_key = if keys.len() > limit {
+            limit
+                .checked_sub(1)
+                .and_then(|last| keys.get(last))
+                .map(|(value, owner)| (U128(*value), owner.clone()))
+        } else {
+            None
+        };
+        let projects = keys
+            .into_iter()
+            .take(limit)
+            .map(|(_, owner)| self.internal_get_project(&(round_id, owner)).unwrap())
+            .collect();
+        ProjectPage { projects, next_key }
+    }
+}
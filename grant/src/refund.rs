@@ -0,0 +1,126 @@
+use near_contract_standards::fungible_token::core::ext_ft_core;
+use near_sdk::json_types::U128;
+use near_sdk::{env, is_promise_success, near_bindgen, require, AccountId, Promise};
+
+use crate::*;
+
+impl Contract {
+    /// Records `amount` paid into the round by `donor_id` with the platform fee,
+    /// so it can be refunded.
+    pub(crate) fn internal_add_donation(
+        &mut self,
+        donor_id: &AccountId,
+        round_id: RoundId,
+        token_id: &Option<AccountId>,
+        amount: u128,
+    ) {
+        match token_id {
+            None => {
+                let key = (donor_id.clone(), round_id);
+                let donated = self.donations.get(&key).map_or(0, |amount| amount.0);
+                self.donations.insert(&key, &U128(donated + amount));
+            }
+            Some(token_id) => {
+                let key = (donor_id.clone(), round_id, token_id.clone());
+                let donated = self.token_donations.get(&key).map_or(0, |amount| amount.0);
+                self.token_donations.insert(&key, &U128(donated + amount));
+            }
+        }
+    }
+
+    /// Removes grants, platform fees and donations of `account_id` in the round,
+    /// returns their sum.
+    fn internal_take_refund(
+        &mut self,
+        account_id: &AccountId,
+        round_id: RoundId,
+        token_id: &Option<AccountId>,
+    ) -> u128 {
+        let mut amount = 0;
+        for project_id in self.internal_voted_projects(account_id, round_id) {
+            let mut record = self.internal_get_vote(account_id, &project_id);
+            amount += record.take_grants(token_id) + record.take_fees(token_id);
+            self.internal_save_vote(account_id, &project_id, &record);
+        }
+        let donated = match token_id {
+            None => self.donations.remove(&(account_id.clone(), round_id)),
+            Some(token_id) => {
+                self.token_donations.remove(&(account_id.clone(), round_id, token_id.clone()))
+            }
+        };
+        amount += donated.map_or(0, |donated| donated.0);
+        amount
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Abort an unfinished round, nothing is paid out to projects and voters and donors
+    /// can claim refunds including platform fees. Funds added by `sudo_fund_round` or a rollover go to the reserve
+    /// and bonds of open challenges go back. Only can be called by owner.
+    pub fn sudo_cancel_round(&mut self, round_id: RoundId) -> Round {
+        self.assert_owner();
+        self.assert_migrated();
+        let mut round = self.get_round(round_id).expect("ERR_ROUND_NOT_FOUND");
+        require!(round.status == RoundStatus::Active, "ERR_ROUND_NOT_ACTIVE");
+        round.support_pool = U128(round.support_pool.0 - round.funded.0);
+        self.internal_add_reserve(&None, round.funded.0);
+        round.funded = U128(0);
+        for (token_id, funded) in round.token_funded.drain() {
+            let pool = round.token_pools.get_mut(&token_id).unwrap();
+            pool.support_pool = U128(pool.support_pool.0 - funded.0);
+            self.internal_add_reserve(&Some(token_id), funded.0);
+        }
+        self.internal_cancel_challenges(&mut round);
+        round.status = RoundStatus::Cancelled;
+        self.internal_unschedule_round(&round);
+        self.internal_save_round(&round);
+        Event::RoundUpdated { round: &round }.emit();
+        round
+    }
+
+    /// Take back votes and donations to a cancelled round with their platform fees,
+    /// paid in NEAR or in `token_id`.
+    pub fn claim_refund(&mut self, round_id: RoundId, token_id: Option<AccountId>) -> Promise {
+        self.assert_migrated();
+        let account_id = env::predecessor_account_id();
+        let round = self.get_round(round_id).expect("ERR_ROUND_NOT_FOUND");
+        require!(round.status == RoundStatus::Cancelled, "ERR_ROUND_NOT_CANCELLED");
+        let storage_used = env::storage_usage();
+        let amount = U128(self.internal_take_refund(&account_id, round_id, &token_id));
+        require!(amount.0 > 0, "ERR_NOTHING_TO_REFUND");
+        self.internal_remove_votes(&account_id, round_id, |_| true);
+        self.internal_charge_storage(&account_id, storage_used);
+        let transfer = match &token_id {
+            None => Promise::new(account_id.clone()).transfer(amount.0),
+            Some(token_id) => ext_ft_core::ext(token_id.clone())
+                .with_attached_deposit(1)
+                .with_static_gas(GAS_FOR_FT_TRANSFER)
+                .ft_transfer(account_id.clone(), amount, None),
+        };
+        transfer.then(
+            Self::ext(env::current_account_id())
+                .with_static_gas(GAS_FOR_RESOLVE_TRANSFER)
+                .on_claim_refund(round_id, account_id, amount, token_id),
+        )
+    }
+
+    /// Resolves `claim_refund`: on failed transfer `amount` can be claimed again.
+    /// Returns the amount actually refunded.
+    #[private]
+    pub fn on_claim_refund(
+        &mut self,
+        round_id: RoundId,
+        account_id: AccountId,
+        amount: U128,
+        token_id: Option<AccountId>,
+    ) -> U128 {
+        if !is_promise_success() {
+            self.internal_add_donation(&account_id, round_id, &token_id, amount.0);
+            return U128(0);
+        }
+        Event::Refund { account_id: &account_id, round_id, amount, token_id: token_id.as_ref() }
+            .emit();
+        amount
+    }
+}
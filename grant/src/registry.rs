@@ -0,0 +1,208 @@
+use near_sdk::{env, ext_contract, json_types::U128, near_bindgen, AccountId, Balance, Promise};
+use near_sdk::{require, Gas, PromiseOrValue, PromiseResult};
+
+use crate::storage::{PAIR_STORAGE_BYTES, VOTE_STORAGE_BYTES};
+use crate::*;
+
+/// Interface of registries, such as an is-human or KYC registry, that rounds may require.
+#[ext_contract(ext_registry)]
+pub trait Registry {
+    fn is_eligible(&self, account_id: AccountId) -> bool;
+}
+
+/// Answer of the registry, failed calls count as not eligible.
+fn is_eligible_result() -> bool {
+    match env::promise_result(0) {
+        PromiseResult::Successful(value) => serde_json::from_slice(&value).unwrap_or(false),
+        _ => false,
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Set the registry that confirms voters of the round, `None` lets everyone vote.
+    pub fn sudo_set_round_registry(
+        &mut self,
+        round_id: RoundId,
+        registry_id: Option<AccountId>,
+    ) -> Round {
+        self.assert_owner_or_operator();
+        self.assert_migrated();
+        let mut round = self.get_round(round_id).expect("ERR_ROUND_NOT_FOUND");
+        round.registry_id = registry_id;
+        self.internal_save_round(&round);
+        Event::RoundUpdated { round: &round }.emit();
+        round
+    }
+
+    /// Resolves `vote` in a round with a registry. Refunds `deposit` instead of
+    /// panicking when the voter is not eligible or the vote can not be applied anymore.
+    #[private]
+    pub fn on_eligible_vote(
+        &mut self,
+        voter: AccountId,
+        project_id: ProjectId,
+        votes: u64,
+        deposit: U128,
+    ) -> Option<Project> {
+        if is_eligible_result() && self.internal_can_vote(&voter, &project_id, votes, deposit.0) {
+            return Some(self.internal_near_vote(voter, project_id, votes, deposit.0));
+        }
+        Event::VoteRefunded {
+            voter_id: &voter,
+            project_id: &project_id,
+            amount: deposit,
+            token_id: None,
+        }
+        .emit();
+        Promise::new(voter).transfer(deposit.0);
+        None
+    }
+
+    /// Resolves a fungible token vote in a round with a registry, returns unused amount.
+    #[private]
+    pub fn on_eligible_token_vote(
+        &mut self,
+        token_id: AccountId,
+        voter: AccountId,
+        amount: U128,
+        project_id: ProjectId,
+        votes: u64,
+    ) -> U128 {
+        if is_eligible_result() {
+            return self.internal_token_vote(token_id, voter, amount, project_id, votes);
+        }
+        Event::VoteRefunded {
+            voter_id: &voter,
+            project_id: &project_id,
+            amount,
+            token_id: Some(&token_id),
+        }
+        .emit();
+        amount
+    }
+}
+
+impl Contract {
+    /// Whether the vote can be applied without panicking, so `on_eligible_vote`
+    /// refunds the deposit instead of losing it.
+    fn internal_can_vote(
+        &self,
+        voter: &AccountId,
+        project_id: &ProjectId,
+        votes: u64,
+        deposit: Balance,
+    ) -> bool {
+        let round = self.get_round(project_id.0).expect("ERR_ROUND_NOT_FOUND");
+        let gas = GAS_FOR_APPLY_VOTE.0 + self.internal_pairs_to_update(&round, project_id).0 .0;
+        self.internal_vote_error(voter, project_id, votes, deposit).is_none()
+            && env::prepaid_gas().0 - env::used_gas().0 >= gas
+    }
+
+    /// Error the vote would fail with, checked before calling the registry.
+    pub(crate) fn internal_vote_error(
+        &self,
+        voter: &AccountId,
+        project_id: &ProjectId,
+        votes: u64,
+        deposit: Balance,
+    ) -> Option<&'static str> {
+        let round = self.get_round(project_id.0).expect("ERR_ROUND_NOT_FOUND");
+        if !round.is_active() {
+            return Some("ERR_ROUND_NOT_ACTIVE");
+        }
+        let approved = self
+            .internal_get_project(project_id)
+            .is_some_and(|project| project.status == ProjectStatus::Approved);
+        if !approved {
+            return Some("ERR_PROJECT_NOT_APPROVED");
+        }
+        if let Some(max_votes) = round.params.max_votes_per_voter {
+            let round_votes =
+                self.round_votes.get(&(voter.clone(), round.id)).map_or(0, |round| round.votes);
+            if round_votes + votes > max_votes {
+                return Some("ERR_TOO_MANY_VOTES");
+            }
+        }
+        if !self.internal_has_room_for(&round, project_id, voter) {
+            return Some("ERR_TOO_MANY_VOTERS");
+        }
+        let cost = self.internal_vote_weight(voter, project_id, votes) as u128 * round.vote_cost.0;
+        if cost > deposit {
+            return Some("ERR_NOT_ENOUGH");
+        }
+        let account = match self.storage_accounts.get(voter) {
+            Some(account) => account,
+            None => return Some("ERR_STORAGE_NOT_REGISTERED"),
+        };
+        let storage = if self.internal_get_vote(voter, project_id).votes == 0 {
+            let pairs = self.internal_pairs_to_update(&round, project_id).1;
+            VOTE_STORAGE_BYTES + pairs * PAIR_STORAGE_BYTES
+        } else {
+            0
+        };
+        if account.available() < storage as Balance * env::STORAGE_PRICE_PER_BYTE {
+            return Some("ERR_NOT_ENOUGH_STORAGE_DEPOSIT");
+        }
+        None
+    }
+
+    /// Whether `voter` already votes for the project or it has less than
+    /// `max_voters_per_project` voters.
+    pub(crate) fn internal_has_room_for(
+        &self,
+        round: &Round,
+        project_id: &ProjectId,
+        voter: &AccountId,
+    ) -> bool {
+        let max_voters = match round.params.max_voters_per_project {
+            Some(max_voters) => max_voters as u64,
+            None => return true,
+        };
+        let voters = self.project_voters.get(project_id).expect("ERR_PROJECT_NOT_FOUND");
+        voters.len() < max_voters || voters.get(voter).is_some()
+    }
+
+    /// Gas and count of the pair weights a vote for the project updates.
+    pub(crate) fn internal_pairs_to_update(
+        &self,
+        round: &Round,
+        project_id: &ProjectId,
+    ) -> (Gas, u64) {
+        if !matches!(round.matching, MatchingStrategy::PairwiseBounded { .. }) {
+            return (Gas(0), 0);
+        }
+        let pairs = self.project_voters.get(project_id).map_or(0, |voters| voters.len());
+        (Gas(pairs * GAS_FOR_PAIR_WEIGHT.0), pairs)
+    }
+
+    /// Checks the voter in the round registry before `FtMessage::Vote` is applied.
+    pub(crate) fn internal_token_vote_checked(
+        &mut self,
+        token_id: AccountId,
+        voter: AccountId,
+        amount: U128,
+        project_id: ProjectId,
+        votes: u64,
+    ) -> PromiseOrValue<U128> {
+        let round = self.get_round(project_id.0).expect("ERR_ROUND_NOT_FOUND");
+        match round.registry_id.clone() {
+            Some(registry_id) => {
+                require!(round.is_active(), "ERR_ROUND_NOT_ACTIVE");
+                let pairs_gas = self.internal_pairs_to_update(&round, &project_id).0;
+                ext_registry::ext(registry_id)
+                    .with_static_gas(GAS_FOR_IS_ELIGIBLE)
+                    .is_eligible(voter.clone())
+                    .then(
+                        Self::ext(env::current_account_id())
+                            .with_static_gas(GAS_FOR_RESOLVE_VOTE + pairs_gas)
+                            .on_eligible_token_vote(token_id, voter, amount, project_id, votes),
+                    )
+                    .into()
+            }
+            None => PromiseOrValue::Value(
+                self.internal_token_vote(token_id, voter, amount, project_id, votes),
+            ),
+        }
+    }
+}
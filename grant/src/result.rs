@@ -0,0 +1,111 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
+use near_sdk::{near_bindgen, AccountId};
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// Outcome of a project written when its round is finished, payouts only read it.
+#[witgen::witgen]
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone)]
+pub struct RoundResult {
+    pub project_id: ProjectId,
+    /// Paid directly by voters in NEAR after the platform fee.
+    pub grants: U128,
+    /// Part of the NEAR matching pool.
+    pub matched: U128,
+    /// Share of the NEAR matching pool in basis points.
+    pub share_point: u32,
+    /// Place in the round by estimated match, starting from 1.
+    pub rank: u32,
+    pub token_grants: HashMap<AccountId, U128>,
+    pub token_matched: HashMap<AccountId, U128>,
+}
+
+impl RoundResult {
+    pub fn grants_of(&self, token_id: &Option<AccountId>) -> u128 {
+        match token_id {
+            None => self.grants.0,
+            Some(token_id) => self.token_grants.get(token_id).map_or(0, |amount| amount.0),
+        }
+    }
+
+    pub fn matched_of(&self, token_id: &Option<AccountId>) -> u128 {
+        match token_id {
+            None => self.matched.0,
+            Some(token_id) => self.token_matched.get(token_id).map_or(0, |amount| amount.0),
+        }
+    }
+}
+
+impl Contract {
+    /// Freezes grants and matches of up to `limit` more projects of the round in the order
+    /// of the estimated match ranking, skipping projects which are not approved.
+    /// Returns whether all projects are done.
+    pub(crate) fn internal_write_results(&mut self, round: &mut Round, limit: u32) -> bool {
+        let pool = round.support_pool.0;
+        let ranking = self.internal_get_ranking(round.id, ProjectSort::EstimatedMatch);
+        let take = (limit as usize).saturating_add(1);
+        let keys: Vec<(u128, AccountId)> = match round.rank_cursor.clone() {
+            None => ranking.iter_rev().map(|(key, _)| key).take(take).collect(),
+            Some((value, owner)) => {
+                ranking.iter_rev_from((value.0, owner)).map(|(key, _)| key).take(take).collect()
+            }
+        };
+        let done = keys.len() <= limit as usize;
+        for (value, owner) in keys.into_iter().take(limit as usize) {
+            round.rank_cursor = Some((U128(value), owner.clone()));
+            let project_id = (round.id, owner);
+            let project = self.internal_get_project(&project_id).unwrap();
+            if project.status != ProjectStatus::Approved {
+                continue;
+            }
+            round.ranked_projects += 1;
+            let matched = round.matched_of(&project, &None);
+            let result = RoundResult {
+                grants: project.grants,
+                matched: U128(matched),
+                share_point: (matched * 10000).checked_div(pool).unwrap_or(0) as u32,
+                rank: round.ranked_projects,
+                token_grants: project.token_grants.clone(),
+                token_matched: round
+                    .token_pools
+                    .keys()
+                    .map(|token_id| {
+                        let matched = round.matched_of(&project, &Some(token_id.clone()));
+                        (token_id.clone(), U128(matched))
+                    })
+                    .collect(),
+                project_id,
+            };
+            round.add_matched(&None, matched);
+            for (token_id, matched) in &result.token_matched {
+                round.add_matched(&Some(token_id.clone()), matched.0);
+            }
+            self.ranked_results.insert(&(round.id, result.rank), &result.project_id);
+            self.round_results.insert(&result.project_id, &result);
+        }
+        done
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Results of a finished round ordered by rank.
+    pub fn get_round_results(
+        &self,
+        round_id: RoundId,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> Vec<RoundResult> {
+        let from = offset.unwrap_or(0).saturating_add(1);
+        (from..=from.saturating_add(limit.unwrap_or(u32::MAX)).saturating_sub(1))
+            .map_while(|rank| self.ranked_results.get(&(round_id, rank)))
+            .map(|project_id| self.round_results.get(&project_id).unwrap())
+            .collect()
+    }
+
+    pub fn get_round_result(&self, project_id: ProjectId) -> Option<RoundResult> {
+        self.round_results.get(&project_id)
+    }
+}
@@ -0,0 +1,116 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
+use near_sdk::{near_bindgen, require, AccountId};
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// Where undistributed matching funds of a finished round go.
+#[witgen::witgen]
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, PartialEq, Debug, Clone)]
+pub enum RolloverTarget {
+    Round {
+        round_id: RoundId,
+    },
+    /// Contract reserve, spent on rounds with `sudo_fund_round`.
+    Reserve,
+}
+
+/// Matching funds a finished round did not distribute, in NEAR and in tokens.
+#[witgen::witgen]
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone)]
+pub struct Rollover {
+    pub target: RolloverTarget,
+    pub amount: U128,
+    pub token_amounts: HashMap<AccountId, U128>,
+}
+
+impl Contract {
+    pub(crate) fn internal_add_reserve(&mut self, token_id: &Option<AccountId>, amount: u128) {
+        let reserve = self.get_reserve(token_id.clone()).0 + amount;
+        match token_id {
+            None => self.reserve = U128(reserve),
+            Some(token_id) => {
+                self.token_reserves.insert(token_id, &U128(reserve));
+            }
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Move matching funds of a finished round that no project can withdraw, because nobody
+    /// co-voted or because of rounding, to the pool of an active round or to the reserve.
+    pub fn sudo_rollover_round(&mut self, round_id: RoundId, target: RolloverTarget) -> Round {
+        self.assert_owner_or_operator();
+        self.assert_migrated();
+        let mut round = self.get_round(round_id).expect("ERR_ROUND_NOT_FOUND");
+        require!(round.status == RoundStatus::Finished, "ERR_ROUND_NOT_FINISHED");
+        require!(round.rollover.is_none(), "ERR_ROLLED_OVER");
+        let amount = round.undistributed_of(&None);
+        let token_amounts: HashMap<AccountId, U128> = round
+            .token_pools
+            .keys()
+            .map(|token_id| {
+                (token_id.clone(), U128(round.undistributed_of(&Some(token_id.clone()))))
+            })
+            .collect();
+        match &target {
+            RolloverTarget::Round { round_id: target_id } => {
+                require!(*target_id != round_id, "ERR_WRONG_ROUND");
+                let mut target_round = self.get_round(*target_id).expect("ERR_ROUND_NOT_FOUND");
+                require!(target_round.status == RoundStatus::Active, "ERR_ROUND_NOT_ACTIVE");
+                target_round.add_to_pool(&None, amount);
+                for (token_id, amount) in &token_amounts {
+                    target_round.add_to_pool(&Some(token_id.clone()), amount.0);
+                }
+                target_round.rolled_over_from.push(round_id);
+                self.internal_save_round(&target_round);
+                Event::RoundUpdated { round: &target_round }.emit();
+            }
+            RolloverTarget::Reserve => {
+                self.internal_add_reserve(&None, amount);
+                for (token_id, amount) in &token_amounts {
+                    self.internal_add_reserve(&Some(token_id.clone()), amount.0);
+                }
+            }
+        }
+        round.rollover = Some(Rollover { target, amount: U128(amount), token_amounts });
+        self.internal_save_round(&round);
+        Event::RoundRolledOver { round: &round }.emit();
+        round
+    }
+
+    /// Add `amount` of the reserve in NEAR, or in `token_id`, to the pool of an active round.
+    pub fn sudo_fund_round(
+        &mut self,
+        round_id: RoundId,
+        amount: U128,
+        token_id: Option<AccountId>,
+    ) -> Round {
+        self.assert_owner_or_operator();
+        self.assert_migrated();
+        let mut round = self.get_round(round_id).expect("ERR_ROUND_NOT_FOUND");
+        require!(round.status == RoundStatus::Active, "ERR_ROUND_NOT_ACTIVE");
+        let reserve = self.get_reserve(token_id.clone());
+        require!(amount.0 <= reserve.0, "ERR_TOO_MUCH");
+        match &token_id {
+            None => self.reserve = U128(reserve.0 - amount.0),
+            Some(token_id) => {
+                self.token_reserves.insert(token_id, &U128(reserve.0 - amount.0));
+            }
+        }
+        round.add_to_pool(&token_id, amount.0);
+        self.internal_save_round(&round);
+        Event::RoundUpdated { round: &round }.emit();
+        round
+    }
+
+    /// Reserve in NEAR or in `token_id`.
+    pub fn get_reserve(&self, token_id: Option<AccountId>) -> U128 {
+        match token_id {
+            None => self.reserve,
+            Some(token_id) => self.token_reserves.get(&token_id).unwrap_or(U128(0)),
+        }
+    }
+}
@@ -1,21 +1,26 @@
+use crate::token::add_amount;
 use crate::*;
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use std::collections::HashMap;
 
 use near_sdk::require;
 use near_sdk::{env, json_types::U128, near_bindgen};
 use serde::{Deserialize, Serialize};
-use witgen;
 
 #[witgen::witgen]
-#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, PartialEq, Debug)]
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, PartialEq, Debug, Clone)]
 pub enum RoundStatus {
     Active,
     Finished,
+    /// Aborted round, voters and donors take their money back with `claim_refund`.
+    Cancelled,
+    /// Results are being written by `sudo_finish_round`, payouts wait for `Finished`.
+    Finishing,
 }
 
 #[witgen::witgen]
 #[near_bindgen]
-#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, PanicOnDefault)]
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, PanicOnDefault, Clone)]
 pub struct Round {
     pub created_at: u32,
     pub end_at: u32,
@@ -27,86 +32,491 @@ pub struct Round {
     pub vote_cost: U128,
     pub projects: u32,
     pub status: RoundStatus,
+    /// Fungible tokens accepted for votes and donations with their vote cost.
+    pub accepted_tokens: HashMap<AccountId, U128>,
+    pub token_pools: HashMap<AccountId, TokenPool>,
+    pub matching: MatchingStrategy,
+    /// Total match area of projects for strategies other than `Pairwise`.
+    pub match_area: U128,
+    pub tallied_projects: u32,
+    /// Position in the round projects `sudo_tally_round` continues from.
+    pub tally_cursor: u32,
+    /// Next pair of voters of the project at `tally_cursor` and the area of pairs before it.
+    pub tally_pair: (u32, u32),
+    pub tally_area: U128,
+    /// Contract confirming that voters are eligible, see `Registry`.
+    pub registry_id: Option<AccountId>,
+    /// Undistributed matching funds moved out by `sudo_rollover_round`.
+    pub rollover: Option<Rollover>,
+    /// Rounds whose undistributed matching funds were added to this round.
+    pub rolled_over_from: Vec<RoundId>,
+    pub params: RoundParams,
+    /// Projects with challenges waiting for resolution, the round cannot be finished
+    /// until there are none.
+    pub open_challenges: Vec<ProjectId>,
+    /// Matching funds which are not donations, they go back to the reserve on cancel.
+    pub funded: U128,
+    pub token_funded: HashMap<AccountId, U128>,
+    /// Results written by `sudo_finish_round`, the rank of the last one.
+    pub ranked_projects: u32,
+    /// Last project of the estimated match ranking `sudo_finish_round` went through.
+    pub rank_cursor: Option<ProjectCursor>,
+    /// Matched to projects by the results written so far, the rest can be rolled over.
+    pub matched: U128,
+    pub token_matched: HashMap<AccountId, U128>,
+    /// Platform fees taken in the round, collected once it finishes and refunded on cancel.
+    pub fees: U128,
+    pub token_fees: HashMap<AccountId, U128>,
+}
+
+#[witgen::witgen]
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone)]
+pub struct TokenPool {
+    pub pure_support_pool: U128,
+    pub support_pool: U128,
 }
 
 #[witgen::witgen]
 pub type RoundId = u64;
 
+/// Timeline of a round around its voting window from `start_at` to `end_at`.
+#[witgen::witgen]
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, PartialEq, Debug, Clone)]
+pub struct RoundPhases {
+    pub application_start_at: u32,
+    /// Applications close before voting starts.
+    pub application_end_at: u32,
+    /// Review and challenges go on from the end of voting until payouts start.
+    pub payout_at: u32,
+}
+
+#[witgen::witgen]
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub enum RoundPhase {
+    /// Before applications open or between applications and voting.
+    Upcoming,
+    Application,
+    Voting,
+    Review,
+    Payout,
+    Cancelled,
+}
+
+/// Settings of a round given at creation, vote cost and fee default to the contract config.
+#[witgen::witgen]
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Default)]
+pub struct RoundParams {
+    pub vote_cost: Option<U128>,
+    /// Platform fee in basis points.
+    pub fee_point: Option<u32>,
+    /// Most votes one account can give across projects of the round.
+    pub max_votes_per_voter: Option<u64>,
+    /// Most projects that can be approved in the round.
+    pub max_projects: Option<u32>,
+    /// Most NEAR matched to one project, the rest is left for rollover.
+    pub matching_cap: Option<U128>,
+    /// Without phases projects apply until voting ends and payouts start after the review.
+    pub phases: Option<RoundPhases>,
+    /// Seconds between the end of voting and payouts when there are no `phases`.
+    pub review_duration: Option<u32>,
+    /// Most accounts voting for one project, set for `PairwiseBounded` rounds.
+    pub max_voters_per_project: Option<u32>,
+}
+
 impl Round {
     pub fn is_active(&self) -> bool {
         let now = (env::block_timestamp_ms() / 1_000) as u32;
         self.status == RoundStatus::Active && self.start_at <= now && self.end_at >= now
     }
+
+    pub fn phases(&self) -> RoundPhases {
+        self.params.phases.clone().unwrap_or(RoundPhases {
+            application_start_at: self.created_at,
+            application_end_at: self.end_at,
+            payout_at: self.end_at + 1 + self.params.review_duration.unwrap_or(0),
+        })
+    }
+
+    pub fn phase(&self) -> RoundPhase {
+        let now = (env::block_timestamp_ms() / 1_000) as u32;
+        let phases = self.phases();
+        if self.status == RoundStatus::Cancelled {
+            RoundPhase::Cancelled
+        } else if self.status == RoundStatus::Finished || self.status == RoundStatus::Finishing {
+            RoundPhase::Payout
+        } else if self.is_active() {
+            RoundPhase::Voting
+        } else if self.is_accepting_projects() {
+            RoundPhase::Application
+        } else if now < self.start_at {
+            RoundPhase::Upcoming
+        } else if now < phases.payout_at {
+            RoundPhase::Review
+        } else {
+            RoundPhase::Payout
+        }
+    }
+
+    /// Whether new projects can apply to the round.
+    pub fn is_accepting_projects(&self) -> bool {
+        let now = (env::block_timestamp_ms() / 1_000) as u32;
+        let phases = self.phases();
+        self.status == RoundStatus::Active
+            && phases.application_start_at <= now
+            && phases.application_end_at >= now
+    }
+
+    fn assert_valid_timeline(&self) {
+        require!(self.start_at < self.end_at, "ERR_WRONG_END_AT");
+        if let Some(phases) = &self.params.phases {
+            require!(
+                phases.application_start_at <= phases.application_end_at
+                    && phases.application_end_at < self.start_at
+                    && self.end_at < phases.payout_at,
+                "ERR_WRONG_PHASES"
+            );
+        }
+    }
+
+    pub fn token_vote_cost(&self, token_id: &AccountId) -> U128 {
+        *self.accepted_tokens.get(token_id).expect("ERR_TOKEN_NOT_ACCEPTED")
+    }
+
+    pub fn support_pool_of(&self, token_id: &Option<AccountId>) -> u128 {
+        match token_id {
+            None => self.support_pool.0,
+            Some(token_id) => self.token_pools.get(token_id).map_or(0, |pool| pool.support_pool.0),
+        }
+    }
+
+    /// Platform fee of the round in basis points, rounds created before
+    /// `RoundParams` use the contract `default` one.
+    pub fn fee_point(&self, default: u32) -> u32 {
+        self.params.fee_point.unwrap_or(default)
+    }
+
+    /// Adds `amount` of the NEAR pool, or of `token_id` pool, matched to a project.
+    pub fn add_matched(&mut self, token_id: &Option<AccountId>, amount: u128) {
+        match token_id {
+            None => self.matched = U128(self.matched.0 + amount),
+            Some(token_id) => {
+                let matched = self.token_matched.entry(token_id.clone()).or_insert(U128(0));
+                *matched = U128(matched.0 + amount);
+            }
+        }
+    }
+
+    /// Adds a platform fee taken in NEAR or in `token_id`.
+    pub fn add_fee(&mut self, token_id: &Option<AccountId>, amount: u128) {
+        match token_id {
+            None => self.fees = U128(self.fees.0 + amount),
+            Some(token_id) => add_amount(&mut self.token_fees, token_id, amount),
+        }
+    }
+
+    /// Removes `amount` of platform fees in NEAR or in `token_id`.
+    pub fn remove_fee(&mut self, token_id: &Option<AccountId>, amount: u128) {
+        match token_id {
+            None => self.fees = U128(self.fees.0 - amount),
+            Some(token_id) => {
+                let fees = self.token_fees.get_mut(token_id).unwrap();
+                *fees = U128(fees.0 - amount);
+            }
+        }
+    }
+
+    /// Part of the NEAR pool, or of `token_id` pool, which is not matched to any project.
+    pub fn undistributed_of(&self, token_id: &Option<AccountId>) -> u128 {
+        let matched = match token_id {
+            None => self.matched.0,
+            Some(token_id) => self.token_matched.get(token_id).map_or(0, |matched| matched.0),
+        };
+        self.support_pool_of(token_id) - matched
+    }
+
+    /// Adds matching funds which are not donations, so `pure_support_pool` is left as is.
+    pub fn add_to_pool(&mut self, token_id: &Option<AccountId>, amount: u128) {
+        match token_id {
+            None => {
+                self.support_pool = U128(self.support_pool.0 + amount);
+                self.funded = U128(self.funded.0 + amount);
+            }
+            Some(token_id) => {
+                let funded = self.token_funded.entry(token_id.clone()).or_insert(U128(0));
+                *funded = U128(funded.0 + amount);
+                let pool = self
+                    .token_pools
+                    .entry(token_id.clone())
+                    .or_insert(TokenPool { pure_support_pool: U128(0), support_pool: U128(0) });
+                pool.support_pool = U128(pool.support_pool.0 + amount);
+            }
+        }
+    }
+}
+
+impl Contract {
+    pub(crate) fn internal_save_round(&mut self, round: &Round) {
+        self.rounds.insert(&round.id, &VersionedRound::Current(round.clone()));
+    }
+
+    /// Puts the round on the calendar, it must not overlap any scheduled round.
+    pub(crate) fn internal_schedule_round(&mut self, round: &Round) {
+        require!(self.internal_is_slot_free(round), "ERR_ROUND_OVERLAP");
+        self.schedule.insert(&round.start_at, &round.id);
+    }
+
+    /// Whether no scheduled round overlaps the round.
+    pub(crate) fn internal_is_slot_free(&self, round: &Round) -> bool {
+        self.schedule.floor_key(&round.end_at).is_none_or(|start_at| {
+            let previous = self.get_round(self.schedule.get(&start_at).unwrap()).unwrap();
+            previous.end_at < round.start_at
+        })
+    }
+
+    /// Frees the slot of the round on the calendar, returns whether it was scheduled.
+    pub(crate) fn internal_unschedule_round(&mut self, round: &Round) -> bool {
+        if self.schedule.get(&round.start_at) != Some(round.id) {
+            return false;
+        }
+        self.schedule.remove(&round.start_at);
+        true
+    }
+
+    /// Given round or the scheduled round open at the block time.
+    pub(crate) fn internal_round_id(&self, round_id: Option<RoundId>) -> RoundId {
+        round_id
+            .or_else(|| self.get_current_round().map(|round| round.id))
+            .expect("ERR_NO_CURRENT_ROUND")
+    }
+
+    /// Given round or the first scheduled round taking applications at the block time,
+    /// applications of phased rounds close before voting starts.
+    pub(crate) fn internal_application_round_id(&self, round_id: Option<RoundId>) -> RoundId {
+        round_id
+            .or_else(|| {
+                let now = (env::block_timestamp_ms() / 1_000) as u32;
+                let current = self.schedule.floor_key(&now).and_then(|at| self.schedule.get(&at));
+                current
+                    .into_iter()
+                    .chain(self.schedule.iter_from(now).map(|(_, round_id)| round_id))
+                    .filter_map(|round_id| self.get_round(round_id))
+                    .find(Round::is_accepting_projects)
+                    .map(|round| round.id)
+            })
+            .expect("ERR_NO_CURRENT_ROUND")
+    }
 }
 
 #[near_bindgen]
 impl Contract {
+    /// Schedules a round of default duration right after the last scheduled one,
+    /// or starting now if the calendar is empty or over.
     pub fn sudo_new_default_round(&mut self) -> Round {
-        let start_at = (env::block_timestamp_ms() / 1_000) as u32;
-        let end_at = start_at + self.default_duration;
-        self.sudo_new_round(start_at, end_at)
+        let now = (env::block_timestamp_ms() / 1_000) as u32;
+        let start_at = self.schedule.max().map_or(now, |start_at| {
+            let last = self.get_round(self.schedule.get(&start_at).unwrap()).unwrap();
+            now.max(last.end_at + 1)
+        });
+        let round = self.sudo_new_round(start_at, start_at + self.default_duration, None, None);
+        self.internal_schedule_round(&round);
+        round
+    }
+
+    /// Creates a round on the calendar, it becomes the current round between
+    /// `start_at` and `end_at` without further calls.
+    pub fn sudo_schedule_round(
+        &mut self,
+        start_at: u32,
+        end_at: u32,
+        matching: Option<MatchingStrategy>,
+        params: Option<RoundParams>,
+    ) -> Round {
+        let now = (env::block_timestamp_ms() / 1_000) as u32;
+        require!(start_at >= now, "ERR_WRONG_START_AT");
+        let round = self.sudo_new_round(start_at, end_at, matching, params);
+        self.internal_schedule_round(&round);
+        round
     }
 
-    pub fn sudo_new_round(&mut self, start_at: u32, end_at: u32) -> Round {
+    /// Creates a round off the calendar, it never becomes the current round, so it has
+    /// to be given by id to `donate`, `new_project` and views. See `sudo_schedule_round`.
+    pub fn sudo_new_round(
+        &mut self,
+        start_at: u32,
+        end_at: u32,
+        matching: Option<MatchingStrategy>,
+        params: Option<RoundParams>,
+    ) -> Round {
         self.assert_owner_or_operator();
+        self.assert_migrated();
         require!(start_at < end_at, "ERR_WRONG_END_AT");
-        if let Some(round) = self.rounds.get(&self.current_round_id) {
-            require!(!round.is_active(), "ERR_ALREADY_ACTIVE_ROUND");
-        };
-        self.current_round_id += 1;
+        let mut params = params.unwrap_or_default();
+        let vote_cost = *params.vote_cost.get_or_insert(self.default_vote_cost);
+        let fee_point = *params.fee_point.get_or_insert(self.fee_point);
+        params.review_duration.get_or_insert(self.default_review_duration);
+        require!(vote_cost.0 > 0, "ERR_WRONG_VOTE_COST");
+        require!(fee_point <= 10000, "ERR_WRONG_FEE_POINT");
+        let matching = matching.unwrap_or(MatchingStrategy::Pairwise);
+        if matches!(matching, MatchingStrategy::PairwiseBounded { .. }) {
+            let max_voters = *params.max_voters_per_project.get_or_insert(MAX_VOTERS_PER_PROJECT);
+            require!(max_voters <= MAX_VOTERS_PER_PROJECT, "ERR_WRONG_MAX_VOTERS");
+        }
+        self.last_round_id += 1;
         let round = Round {
-            id: self.current_round_id,
+            id: self.last_round_id,
             created_at: (env::block_timestamp_ms() / 1_000) as u32,
             start_at,
             end_at,
             status: RoundStatus::Active,
-            vote_cost: self.default_vote_cost,
+            vote_cost,
             support_pool: 0.into(),
             pure_support_pool: 0.into(),
             projects: 0,
             support_area: 0,
+            accepted_tokens: HashMap::new(),
+            token_pools: HashMap::new(),
+            matching,
+            match_area: U128(0),
+            tallied_projects: 0,
+            tally_cursor: 0,
+            tally_pair: (0, 0),
+            tally_area: U128(0),
+            registry_id: None,
+            rollover: None,
+            rolled_over_from: vec![],
+            params,
+            open_challenges: vec![],
+            funded: U128(0),
+            token_funded: HashMap::new(),
+            ranked_projects: 0,
+            rank_cursor: None,
+            matched: U128(0),
+            token_matched: HashMap::new(),
+            fees: U128(0),
+            token_fees: HashMap::new(),
         };
-        self.rounds.insert(&self.current_round_id, &round);
+        round.assert_valid_timeline();
+        self.internal_save_round(&round);
         self.round_projects.insert(
-            &self.current_round_id,
-            &UnorderedSet::new(StorageKey::ProjectsPerRound { round_id: self.current_round_id }),
+            &round.id,
+            &UnorderedSet::new(StorageKey::ProjectsPerRound { round_id: round.id }),
         );
+        Event::RoundCreated { round: &round }.emit();
         round
     }
 
-    pub fn sudo_update_current_round(
+    /// Moves the voting window of an active round. The status only changes through
+    /// `sudo_finish_round` and `sudo_cancel_round`, `end_at` is fixed once voting is over.
+    pub fn sudo_update_round(
         &mut self,
+        round_id: RoundId,
         danger: bool,
-        status: Option<RoundStatus>,
         start_at: Option<u32>,
         end_at: Option<u32>,
     ) -> Round {
         self.assert_owner();
+        self.assert_migrated();
         require!(danger, "ERR_DO_NOT_PLAY_WITH_ME");
-        let mut round = self.get_round(self.current_round_id).expect("ERR_ROUND_NOT_FOUND");
-        update_if_some!(round, status);
+        let mut round = self.get_round(round_id).expect("ERR_ROUND_NOT_FOUND");
+        require!(round.status == RoundStatus::Active, "ERR_ROUND_NOT_ACTIVE");
+        if end_at.is_some() {
+            // Votes and the tally are final once voting is over.
+            let now = (env::block_timestamp_ms() / 1_000) as u32;
+            require!(round.tally_cursor == 0 && now <= round.end_at, "ERR_VOTING_ENDED");
+        }
+        let scheduled = self.internal_unschedule_round(&round);
         update_if_some!(round, start_at);
         update_if_some!(round, end_at);
-        require!(round.start_at <= round.end_at, "ERR_WRONG_END_AT");
-        self.rounds.insert(&self.current_round_id, &round);
+        round.assert_valid_timeline();
+        if scheduled {
+            self.internal_schedule_round(&round);
+        }
+        self.internal_save_round(&round);
+        Event::RoundUpdated { round: &round }.emit();
         round
     }
 
-    pub fn sudo_finish_current_round(&mut self) -> Round {
+    /// Writes results of up to `limit` more projects of a reviewed and tallied round.
+    /// Call until the round is `Finished`, payouts start after that.
+    pub fn sudo_finish_round(&mut self, round_id: RoundId, limit: Option<u32>) -> Round {
+        self.assert_owner_or_operator();
+        self.assert_migrated();
+        let mut round = self.get_round(round_id).expect("ERR_ROUND_NOT_FOUND");
+        if round.status == RoundStatus::Active {
+            require!(!round.is_active(), "ERR_ROUND_ACTIVE");
+            require!(round.phase() == RoundPhase::Payout, "ERR_ROUND_IN_REVIEW");
+            require!(round.open_challenges.is_empty(), "ERR_OPEN_CHALLENGES");
+            require!(round.is_tallied(), "ERR_ROUND_NOT_TALLIED");
+            round.status = RoundStatus::Finishing;
+        }
+        require!(round.status == RoundStatus::Finishing, "ERR_ROUND_NOT_ACTIVE");
+        if self.internal_write_results(&mut round, limit.unwrap_or(u32::MAX)) {
+            round.status = RoundStatus::Finished;
+            self.internal_add_fee(&None, round.fees.0);
+            for (token_id, fees) in &round.token_fees {
+                self.internal_add_fee(&Some(token_id.clone()), fees.0);
+            }
+            self.internal_save_round(&round);
+            Event::RoundFinished { round: &round }.emit();
+        } else {
+            self.internal_save_round(&round);
+            Event::RoundUpdated { round: &round }.emit();
+        }
+        round
+    }
+
+    /// Accept `token_id` in the round with given vote cost, `None` stops accepting it.
+    pub fn sudo_set_round_token(
+        &mut self,
+        round_id: RoundId,
+        token_id: AccountId,
+        vote_cost: Option<U128>,
+    ) -> Round {
         self.assert_owner_or_operator();
-        let mut round = self.get_round(self.current_round_id).expect("ERR_ROUND_NOT_FOUND");
-        require!(!round.is_active(), "ERR_ROUND_ACTIVE");
-        round.status = RoundStatus::Finished;
-        self.rounds.insert(&self.current_round_id, &round);
+        self.assert_migrated();
+        let mut round = self.get_round(round_id).expect("ERR_ROUND_NOT_FOUND");
+        match vote_cost {
+            Some(vote_cost) => {
+                require!(vote_cost.0 > 0, "ERR_WRONG_VOTE_COST");
+                round.accepted_tokens.insert(token_id, vote_cost);
+            }
+            None => {
+                round.accepted_tokens.remove(&token_id);
+            }
+        }
+        self.internal_save_round(&round);
+        Event::RoundUpdated { round: &round }.emit();
         round
     }
 
+    /// Rounds accepting votes right now.
+    pub fn list_active_rounds(&self) -> Vec<Round> {
+        self.rounds.iter().map(|(_round_id, round)| round.into()).filter(Round::is_active).collect()
+    }
+
+    pub fn current_phase(&self, round_id: RoundId) -> RoundPhase {
+        self.get_round(round_id).expect("ERR_ROUND_NOT_FOUND").phase()
+    }
+
+    /// Scheduled round accepting votes at the block time.
     pub fn get_current_round(&self) -> Option<Round> {
-        self.rounds.get(&self.current_round_id)
+        let now = (env::block_timestamp_ms() / 1_000) as u32;
+        let start_at = self.schedule.floor_key(&now)?;
+        self.get_round(self.schedule.get(&start_at).unwrap()).filter(Round::is_active)
+    }
+
+    /// Rounds on the calendar ordered by `start_at`.
+    pub fn list_scheduled_rounds(&self, limit: Option<u32>, offset: Option<u32>) -> Vec<Round> {
+        self.schedule
+            .iter()
+            .skip(offset.unwrap_or(0) as usize)
+            .take(limit.unwrap_or(u32::MAX) as usize)
+            .map(|(_start_at, round_id)| self.get_round(round_id).unwrap())
+            .collect()
     }
 
     pub fn get_round(&self, round_id: RoundId) -> Option<Round> {
-        self.rounds.get(&round_id)
+        self.rounds.get(&round_id).map(Round::from)
     }
 
     pub fn list_rounds(&self, limit: Option<u32>, offset: Option<u32>) -> Vec<Round> {
@@ -116,20 +526,37 @@ impl Contract {
             .iter()
             .skip(offset as usize)
             .take(limit as usize)
-            .map(|(_round_id, round)| round)
+            .map(|(_round_id, round)| round.into())
             .collect()
     }
 
+    /// Adds the deposit to the matching pool of the round, the current one if not given.
     #[payable]
-    pub fn donate(&mut self) -> Round {
-        let mut round: Round = self.get_round(self.current_round_id).expect("ERR_ROUND_NOT_FOUND");
+    pub fn donate(&mut self, round_id: Option<RoundId>) -> Round {
+        self.assert_migrated();
+        let storage_used = env::storage_usage();
+        let round_id = self.internal_round_id(round_id);
+        let mut round: Round = self.get_round(round_id).expect("ERR_ROUND_NOT_FOUND");
         require!(round.is_active(), "ERR_ROUND_NOT_ACTIVE");
         let deposit = env::attached_deposit();
-        let platform_fee = deposit * (self.fee_point as u128) / 10000;
+        let platform_fee = deposit * round.fee_point(self.fee_point) as u128 / 10000;
         let donate = deposit - platform_fee;
+        round.add_fee(&None, platform_fee);
         round.support_pool = U128(round.support_pool.0 + donate);
         round.pure_support_pool = U128(round.pure_support_pool.0 + deposit);
-        self.rounds.insert(&self.current_round_id, &round);
+        self.internal_save_round(&round);
+        self.internal_add_donation(&env::predecessor_account_id(), round_id, &None, deposit);
+        let storage_cost =
+            self.internal_charge_storage(&env::predecessor_account_id(), storage_used);
+        Event::Donation {
+            donor_id: &env::predecessor_account_id(),
+            round_id,
+            amount: U128(deposit),
+            platform_fee: U128(platform_fee),
+            storage_cost: U128(storage_cost),
+            token_id: None,
+        }
+        .emit();
         round
     }
 }
@@ -0,0 +1,161 @@
+use near_contract_standards::storage_management::{
+    StorageBalance, StorageBalanceBounds, StorageManagement,
+};
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::{assert_one_yocto, env, near_bindgen, require, AccountId, Balance, Promise};
+use near_sdk::{json_types::U128, StorageUsage};
+
+use crate::*;
+
+/// Largest possible entry of `storage_accounts`: prefix, account id, `StorageAccount`
+/// and the per-record overhead.
+pub const ACCOUNT_STORAGE_BYTES: StorageUsage = 1 + 4 + 64 + 16 + 8 + 40;
+
+/// Largest storage of a first vote for a project: the vote record, the round votes of the
+/// voter with the two records of its projects set and the three records of the project
+/// voters map.
+pub const VOTE_STORAGE_BYTES: StorageUsage =
+    (1 + 144 + 48 + 40) + (1 + 76 + 8 + 172 + 40) + 2 * (78 + 68 + 8 + 40) + 3 * (78 + 68 + 8 + 40);
+
+/// Largest entry of `pair_weights`: prefix, round id, two account ids and the weight.
+pub const PAIR_STORAGE_BYTES: StorageUsage = 1 + 8 + 2 * (4 + 64) + 16 + 40;
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct StorageAccount {
+    pub deposit: Balance,
+    pub used: StorageUsage,
+}
+
+impl StorageAccount {
+    pub fn available(&self) -> Balance {
+        self.deposit - self.used as Balance * env::STORAGE_PRICE_PER_BYTE
+    }
+}
+
+impl Contract {
+    /// Charges storage written since `storage_used` to the prepaid balance of `account_id`.
+    /// Returns the cost of the storage taken, freed storage is given back to the balance.
+    pub(crate) fn internal_charge_storage(
+        &mut self,
+        account_id: &AccountId,
+        storage_used: StorageUsage,
+    ) -> Balance {
+        let storage_usage = env::storage_usage();
+        if storage_usage == storage_used {
+            return 0;
+        }
+        let mut account = match self.storage_accounts.get(account_id) {
+            Some(account) => account,
+            None => {
+                require!(storage_usage < storage_used, "ERR_STORAGE_NOT_REGISTERED");
+                return 0;
+            }
+        };
+        if storage_usage > storage_used {
+            account.used += storage_usage - storage_used;
+            require!(
+                account.used as Balance * env::STORAGE_PRICE_PER_BYTE <= account.deposit,
+                "ERR_NOT_ENOUGH_STORAGE_DEPOSIT"
+            );
+        } else {
+            account.used = account.used.saturating_sub(storage_used - storage_usage);
+        }
+        self.storage_accounts.insert(account_id, &account);
+        storage_usage.saturating_sub(storage_used) as Balance * env::STORAGE_PRICE_PER_BYTE
+    }
+
+    fn internal_storage_balance(&self, account: &StorageAccount) -> StorageBalance {
+        StorageBalance { total: U128(account.deposit), available: U128(account.available()) }
+    }
+}
+
+#[near_bindgen]
+impl StorageManagement for Contract {
+    #[payable]
+    fn storage_deposit(
+        &mut self,
+        account_id: Option<AccountId>,
+        registration_only: Option<bool>,
+    ) -> StorageBalance {
+        let amount = env::attached_deposit();
+        let account_id = account_id.unwrap_or_else(env::predecessor_account_id);
+        let min_balance = self.storage_balance_bounds().min.0;
+        let account = match self.storage_accounts.get(&account_id) {
+            Some(mut account) => {
+                if registration_only == Some(true) {
+                    if amount > 0 {
+                        Promise::new(env::predecessor_account_id()).transfer(amount);
+                    }
+                } else {
+                    account.deposit += amount;
+                    self.storage_accounts.insert(&account_id, &account);
+                }
+                account
+            }
+            None => {
+                require!(amount >= min_balance, "ERR_NOT_ENOUGH_STORAGE_DEPOSIT");
+                let deposit = if registration_only == Some(true) {
+                    if amount > min_balance {
+                        Promise::new(env::predecessor_account_id()).transfer(amount - min_balance);
+                    }
+                    min_balance
+                } else {
+                    amount
+                };
+                let storage_used = env::storage_usage();
+                self.storage_accounts.insert(&account_id, &StorageAccount { deposit, used: 0 });
+                let account = StorageAccount { deposit, used: env::storage_usage() - storage_used };
+                self.storage_accounts.insert(&account_id, &account);
+                account
+            }
+        };
+        self.internal_storage_balance(&account)
+    }
+
+    #[payable]
+    fn storage_withdraw(&mut self, amount: Option<U128>) -> StorageBalance {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        let mut account =
+            self.storage_accounts.get(&account_id).expect("ERR_STORAGE_NOT_REGISTERED");
+        let available = account.available();
+        let amount = amount.map_or(available, |amount| amount.0);
+        require!(amount <= available, "ERR_TOO_MUCH");
+        account.deposit -= amount;
+        self.storage_accounts.insert(&account_id, &account);
+        if amount > 0 {
+            Promise::new(account_id).transfer(amount);
+        }
+        self.internal_storage_balance(&account)
+    }
+
+    /// Accounts that still own projects or votes can not be unregistered, even with `force`.
+    #[payable]
+    fn storage_unregister(&mut self, force: Option<bool>) -> bool {
+        assert_one_yocto();
+        let _ = force;
+        let account_id = env::predecessor_account_id();
+        match self.storage_accounts.get(&account_id) {
+            Some(account) => {
+                require!(account.used <= ACCOUNT_STORAGE_BYTES, "ERR_STORAGE_IN_USE");
+                self.storage_accounts.remove(&account_id);
+                Promise::new(account_id).transfer(account.deposit);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn storage_balance_bounds(&self) -> StorageBalanceBounds {
+        StorageBalanceBounds {
+            min: U128(ACCOUNT_STORAGE_BYTES as Balance * env::STORAGE_PRICE_PER_BYTE),
+            max: None,
+        }
+    }
+
+    fn storage_balance_of(&self, account_id: AccountId) -> Option<StorageBalance> {
+        self.storage_accounts
+            .get(&account_id)
+            .map(|account| self.internal_storage_balance(&account))
+    }
+}
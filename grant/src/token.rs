@@ -0,0 +1,126 @@
+use near_contract_standards::fungible_token::receiver::FungibleTokenReceiver;
+use near_sdk::require;
+use near_sdk::{env, json_types::U128, near_bindgen, AccountId, PromiseOrValue};
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// `msg` of `ft_transfer_call` to the grant contract.
+#[witgen::witgen]
+#[derive(Serialize, Deserialize)]
+pub enum FtMessage {
+    Vote { project_id: ProjectId, votes: u64 },
+    Donate { round_id: RoundId },
+}
+
+pub(crate) fn add_amount(
+    amounts: &mut HashMap<AccountId, U128>,
+    token_id: &AccountId,
+    amount: u128,
+) {
+    let current = amounts.get(token_id).map_or(0, |amount| amount.0);
+    amounts.insert(token_id.clone(), U128(current + amount));
+}
+
+impl Contract {
+    pub(crate) fn internal_token_vote(
+        &mut self,
+        token_id: AccountId,
+        voter: AccountId,
+        amount: U128,
+        project_id: ProjectId,
+        votes: u64,
+    ) -> U128 {
+        let storage_used = env::storage_usage();
+        let (mut project, weight) = self.internal_vote(&voter, &project_id, votes);
+        let mut round: Round = self.get_round(project_id.0).expect("ERR_ROUND_NOT_FOUND");
+        let vote_cost = round.token_vote_cost(&token_id);
+
+        let cost = weight as u128 * vote_cost.0;
+        require!(cost <= amount.0, "ERR_NOT_ENOUGH");
+        let platform_fee = cost * round.fee_point(self.fee_point) as u128 / 10000;
+        let grants = cost - platform_fee;
+
+        round.add_fee(&Some(token_id.clone()), platform_fee);
+        self.internal_save_round(&round);
+
+        self.internal_add_grant(&voter, &project_id, &Some(token_id.clone()), grants, platform_fee);
+
+        add_amount(&mut project.token_grants, &token_id, grants);
+        self.internal_save_project(&project_id, &project);
+        let storage_cost = self.internal_charge_storage(&voter, storage_used);
+        Event::Vote {
+            voter_id: &voter,
+            project_id: &project_id,
+            votes,
+            cost: U128(cost),
+            platform_fee: U128(platform_fee),
+            storage_cost: U128(storage_cost),
+            refund: U128(amount.0 - cost),
+            token_id: Some(&token_id),
+        }
+        .emit();
+        U128(amount.0 - cost)
+    }
+
+    fn internal_token_donate(
+        &mut self,
+        token_id: AccountId,
+        donor_id: AccountId,
+        amount: U128,
+        round_id: RoundId,
+    ) -> U128 {
+        let storage_used = env::storage_usage();
+        let mut round: Round = self.get_round(round_id).expect("ERR_ROUND_NOT_FOUND");
+        require!(round.is_active(), "ERR_ROUND_NOT_ACTIVE");
+        require!(round.accepted_tokens.contains_key(&token_id), "ERR_TOKEN_NOT_ACCEPTED");
+        let platform_fee = amount.0 * round.fee_point(self.fee_point) as u128 / 10000;
+        let donate = amount.0 - platform_fee;
+
+        round.add_fee(&Some(token_id.clone()), platform_fee);
+        let mut pool = round
+            .token_pools
+            .get(&token_id)
+            .cloned()
+            .unwrap_or(TokenPool { pure_support_pool: U128(0), support_pool: U128(0) });
+        pool.support_pool = U128(pool.support_pool.0 + donate);
+        pool.pure_support_pool = U128(pool.pure_support_pool.0 + amount.0);
+        round.token_pools.insert(token_id.clone(), pool);
+        self.internal_save_round(&round);
+        self.internal_add_donation(&donor_id, round_id, &Some(token_id.clone()), amount.0);
+        let storage_cost = self.internal_charge_storage(&donor_id, storage_used);
+        Event::Donation {
+            donor_id: &donor_id,
+            round_id,
+            amount,
+            platform_fee: U128(platform_fee),
+            storage_cost: U128(storage_cost),
+            token_id: Some(&token_id),
+        }
+        .emit();
+        U128(0)
+    }
+}
+
+#[near_bindgen]
+impl FungibleTokenReceiver for Contract {
+    /// Vote or donate with an accepted fungible token, returns unused amount.
+    fn ft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        self.assert_migrated();
+        let token_id = env::predecessor_account_id();
+        let message: FtMessage = serde_json::from_str(&msg).expect("ERR_WRONG_MSG");
+        match message {
+            FtMessage::Vote { project_id, votes } => {
+                self.internal_token_vote_checked(token_id, sender_id, amount, project_id, votes)
+            }
+            FtMessage::Donate { round_id } => PromiseOrValue::Value(
+                self.internal_token_donate(token_id, sender_id, amount, round_id),
+            ),
+        }
+    }
+}
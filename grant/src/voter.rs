@@ -0,0 +1,307 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::UnorderedSet;
+use near_sdk::json_types::U128;
+use near_sdk::{near_bindgen, AccountId};
+use serde::{Deserialize, Serialize};
+
+use crate::token::add_amount;
+use crate::*;
+
+/// Votes of one account for one project and what it paid for them.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct VoteRecord {
+    pub votes: u64,
+    /// Paid in NEAR after the platform fee.
+    pub grants: U128,
+    pub token_grants: HashMap<AccountId, U128>,
+    /// Platform fees paid for the votes, refunded if the round is cancelled.
+    pub fees: U128,
+    pub token_fees: HashMap<AccountId, U128>,
+}
+
+/// Index of projects an account voted for in one round.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct RoundVotes {
+    /// Votes across projects of the round.
+    pub votes: u64,
+    /// Owners of the voted projects.
+    pub projects: UnorderedSet<AccountId>,
+}
+
+impl RoundVotes {
+    fn new(account_id: &AccountId, round_id: RoundId) -> Self {
+        Self {
+            votes: 0,
+            projects: UnorderedSet::new(StorageKey::VotedProjects {
+                account_id: account_id.clone(),
+                round_id,
+            }),
+        }
+    }
+}
+
+#[witgen::witgen]
+#[derive(Serialize, Deserialize)]
+pub struct AccountVote {
+    pub project_id: ProjectId,
+    pub votes: u64,
+    /// NEAR paid for one more vote for the project.
+    pub next_vote_cost: U128,
+}
+
+#[witgen::witgen]
+#[derive(Serialize, Deserialize)]
+pub struct AccountContribution {
+    pub project_id: ProjectId,
+    pub votes: u64,
+    /// Paid for votes in NEAR after the platform fee.
+    pub grants: U128,
+    pub token_grants: HashMap<AccountId, U128>,
+    pub next_vote_cost: U128,
+}
+
+#[witgen::witgen]
+#[derive(Serialize, Deserialize)]
+pub struct ProjectVoter {
+    pub account_id: AccountId,
+    pub votes: u64,
+}
+
+impl Default for VoteRecord {
+    fn default() -> Self {
+        Self {
+            votes: 0,
+            grants: U128(0),
+            token_grants: HashMap::new(),
+            fees: U128(0),
+            token_fees: HashMap::new(),
+        }
+    }
+}
+
+impl VoteRecord {
+    pub fn grants_of(&self, token_id: &Option<AccountId>) -> u128 {
+        match token_id {
+            None => self.grants.0,
+            Some(token_id) => self.token_grants.get(token_id).map_or(0, |amount| amount.0),
+        }
+    }
+
+    /// Removes grants in NEAR or in `token_id`, returns their sum.
+    pub fn take_grants(&mut self, token_id: &Option<AccountId>) -> u128 {
+        match token_id {
+            None => std::mem::replace(&mut self.grants, U128(0)).0,
+            Some(token_id) => self.token_grants.remove(token_id).map_or(0, |amount| amount.0),
+        }
+    }
+
+    pub fn add_grants(&mut self, token_id: &Option<AccountId>, amount: u128) {
+        match token_id {
+            None => self.grants = U128(self.grants.0 + amount),
+            Some(token_id) => add_amount(&mut self.token_grants, token_id, amount),
+        }
+    }
+
+    /// Removes platform fees in NEAR or in `token_id`, returns their sum.
+    pub fn take_fees(&mut self, token_id: &Option<AccountId>) -> u128 {
+        match token_id {
+            None => std::mem::replace(&mut self.fees, U128(0)).0,
+            Some(token_id) => self.token_fees.remove(token_id).map_or(0, |amount| amount.0),
+        }
+    }
+
+    pub fn add_fees(&mut self, token_id: &Option<AccountId>, amount: u128) {
+        match token_id {
+            None => self.fees = U128(self.fees.0 + amount),
+            Some(token_id) => add_amount(&mut self.token_fees, token_id, amount),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.votes == 0
+            && self.grants.0 == 0
+            && self.token_grants.values().all(|amount| amount.0 == 0)
+    }
+}
+
+impl Contract {
+    pub(crate) fn internal_get_vote(
+        &self,
+        voter: &AccountId,
+        project_id: &ProjectId,
+    ) -> VoteRecord {
+        self.vote_records.get(&(voter.clone(), project_id.clone())).unwrap_or_default()
+    }
+
+    /// Stores the record, or drops it once it is empty, and keeps the round index of the voter.
+    pub(crate) fn internal_save_vote(
+        &mut self,
+        voter: &AccountId,
+        project_id: &ProjectId,
+        record: &VoteRecord,
+    ) {
+        let key = (voter.clone(), project_id.clone());
+        let old = if record.is_empty() {
+            self.vote_records.remove(&key)
+        } else {
+            self.vote_records.insert(&key, record)
+        };
+        let old_votes = old.as_ref().map_or(0, |old| old.votes);
+        let (added, removed) =
+            (old.is_none() && !record.is_empty(), old.is_some() && record.is_empty());
+        if !added && !removed && old_votes == record.votes {
+            return;
+        }
+        let round_key = (voter.clone(), project_id.0);
+        let mut round_votes = self
+            .round_votes
+            .get(&round_key)
+            .unwrap_or_else(|| RoundVotes::new(voter, project_id.0));
+        round_votes.votes = round_votes.votes - old_votes + record.votes;
+        if added {
+            round_votes.projects.insert(&project_id.1);
+        }
+        if removed {
+            round_votes.projects.remove(&project_id.1);
+        }
+        if round_votes.projects.is_empty() {
+            self.round_votes.remove(&round_key);
+        } else {
+            self.round_votes.insert(&round_key, &round_votes);
+        }
+    }
+
+    pub(crate) fn internal_add_grant(
+        &mut self,
+        voter: &AccountId,
+        project_id: &ProjectId,
+        token_id: &Option<AccountId>,
+        amount: u128,
+        fee: u128,
+    ) {
+        let mut record = self.internal_get_vote(voter, project_id);
+        record.add_grants(token_id, amount);
+        record.add_fees(token_id, fee);
+        self.internal_save_vote(voter, project_id, &record);
+    }
+
+    /// Projects of the round `account_id` voted for, ordered by owner.
+    pub(crate) fn internal_voted_projects(
+        &self,
+        account_id: &AccountId,
+        round_id: RoundId,
+    ) -> Vec<ProjectId> {
+        let mut owners = self
+            .round_votes
+            .get(&(account_id.clone(), round_id))
+            .map_or(vec![], |round_votes| round_votes.projects.to_vec());
+        owners.sort();
+        owners.into_iter().map(|owner| (round_id, owner)).collect()
+    }
+
+    /// Drops votes of `account_id` for projects of the round matching `refunded` after they
+    /// were refunded, the voter is no longer a backer of these projects.
+    pub(crate) fn internal_remove_votes(
+        &mut self,
+        account_id: &AccountId,
+        round_id: RoundId,
+        refunded: impl Fn(&ProjectId) -> bool,
+    ) {
+        for project_id in self.internal_voted_projects(account_id, round_id) {
+            let mut record = self.internal_get_vote(account_id, &project_id);
+            if !refunded(&project_id) || record.votes == 0 {
+                continue;
+            }
+            let mut voters = self.project_voters.get(&project_id).expect("ERR_PROJECT_NOT_FOUND");
+            voters.remove(account_id);
+            self.project_voters.insert(&project_id, &voters);
+            let mut project = self.internal_get_project(&project_id).unwrap();
+            project.total_votes -= record.votes;
+            self.internal_save_project(&project_id, &project);
+            record.votes = 0;
+            self.internal_save_vote(account_id, &project_id, &record);
+        }
+    }
+
+    /// Votes of `account_id` for projects of the round ordered by project,
+    /// records only holding unclaimed grants are skipped.
+    fn internal_account_votes(
+        &self,
+        account_id: &AccountId,
+        round_id: RoundId,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> Vec<(ProjectId, VoteRecord)> {
+        self.internal_voted_projects(account_id, round_id)
+            .into_iter()
+            .map(|project_id| {
+                let record = self.internal_get_vote(account_id, &project_id);
+                (project_id, record)
+            })
+            .filter(|(_, record)| record.votes > 0)
+            .skip(offset.unwrap_or(0) as usize)
+            .take(limit.unwrap_or(u32::MAX) as usize)
+            .collect()
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    pub fn get_account_votes(
+        &self,
+        account_id: AccountId,
+        round_id: RoundId,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> Vec<AccountVote> {
+        let round = self.get_round(round_id).expect("ERR_ROUND_NOT_FOUND");
+        self.internal_account_votes(&account_id, round_id, limit, offset)
+            .into_iter()
+            .map(|(project_id, record)| AccountVote {
+                project_id,
+                votes: record.votes,
+                next_vote_cost: U128((record.votes as u128 + 1) * round.vote_cost.0),
+            })
+            .collect()
+    }
+
+    /// Accounts which voted for the project with their votes.
+    pub fn get_project_voters(
+        &self,
+        project_id: ProjectId,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> Vec<ProjectVoter> {
+        let voters = self.project_voters.get(&project_id).expect("ERR_PROJECT_NOT_FOUND");
+        let (keys, values) = (voters.keys_as_vector(), voters.values_as_vector());
+        let from = offset.unwrap_or(0) as u64;
+        let to = voters.len().min(from.saturating_add(limit.unwrap_or(u32::MAX) as u64));
+        (from..to)
+            .map(|index| ProjectVoter {
+                account_id: keys.get(index).unwrap(),
+                votes: values.get(index).unwrap(),
+            })
+            .collect()
+    }
+
+    /// Votes of the account in the round with what they paid per project.
+    pub fn get_account_contributions(
+        &self,
+        account_id: AccountId,
+        round_id: RoundId,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> Vec<AccountContribution> {
+        let round = self.get_round(round_id).expect("ERR_ROUND_NOT_FOUND");
+        self.internal_account_votes(&account_id, round_id, limit, offset)
+            .into_iter()
+            .map(|(project_id, record)| AccountContribution {
+                project_id,
+                votes: record.votes,
+                grants: record.grants,
+                next_vote_cost: U128((record.votes as u128 + 1) * round.vote_cost.0),
+                token_grants: record.token_grants,
+            })
+            .collect()
+    }
+}
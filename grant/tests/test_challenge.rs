@@ -0,0 +1,210 @@
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use near_contract_standards::storage_management::StorageManagement;
+    use near_sdk::json_types::U128;
+    use near_sdk::test_utils::{accounts, VMContextBuilder};
+    use near_sdk::{testing_env, AccountId, PromiseResult, RuntimeFeesConfig, VMConfig, ONE_NEAR};
+
+    use grant::*;
+
+    fn get_context(predecessor_account_id: AccountId) -> VMContextBuilder {
+        let mut builder = VMContextBuilder::new();
+        builder
+            .current_account_id(accounts(0))
+            .signer_account_id(predecessor_account_id.clone())
+            .predecessor_account_id(predecessor_account_id);
+        builder
+    }
+
+    fn at(predecessor_account_id: AccountId, seconds: u64) -> VMContextBuilder {
+        let mut builder = get_context(predecessor_account_id);
+        builder.block_timestamp(seconds * 1_000_000_000);
+        builder
+    }
+
+    fn register_accounts(contract: &mut Contract) {
+        testing_env!(get_context(accounts(0)).attached_deposit(ONE_NEAR).build());
+        for index in 0..6 {
+            contract.storage_deposit(Some(accounts(index)), None);
+        }
+        testing_env!(get_context(accounts(0)).build());
+    }
+
+    /// Round 1 votes from 10 to 100 and is in review until 200. accounts(3) and accounts(4)
+    /// vote once for projects of accounts(1) and accounts(2), accounts(5) donates 1 NEAR.
+    fn setup() -> Contract {
+        setup_with_matching(None)
+    }
+
+    fn setup_with_matching(matching: Option<MatchingStrategy>) -> Contract {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = Contract::init();
+        register_accounts(&mut contract);
+        let phases = RoundPhases { application_start_at: 0, application_end_at: 5, payout_at: 200 };
+        contract.sudo_new_round(
+            10,
+            100,
+            matching,
+            Some(RoundParams { phases: Some(phases), ..Default::default() }),
+        );
+        for owner in [accounts(1), accounts(2)] {
+            testing_env!(get_context(owner.clone()).build());
+            contract.new_project(Some(1), "a".into(), "b".into(), "c".into(), "d".into());
+            testing_env!(get_context(accounts(0)).build());
+            contract.sudo_approve_project((1, owner), None);
+        }
+        for voter in [accounts(3), accounts(4)] {
+            testing_env!(at(voter, 50).attached_deposit(ONE_NEAR).build());
+            contract.vote((1, accounts(1)), 1);
+            contract.vote((1, accounts(2)), 1);
+        }
+        testing_env!(at(accounts(5), 50).attached_deposit(ONE_NEAR).build());
+        contract.donate(Some(1));
+        contract
+    }
+
+    fn challenge(contract: &mut Contract) {
+        testing_env!(at(accounts(5), 150).attached_deposit(CHALLENGE_BOND).build());
+        contract.challenge_project((1, accounts(1)), "sybil votes".into());
+    }
+
+    fn resolve_with(result: PromiseResult) {
+        testing_env!(
+            get_context(accounts(0)).build(),
+            VMConfig::test(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![result],
+        );
+    }
+
+    #[test]
+    fn test_disqualify_tallied_project() {
+        let mut contract = setup_with_matching(Some(MatchingStrategy::PairwiseBounded { m: 1 }));
+        testing_env!(at(accounts(0), 150).build());
+        assert_eq!(contract.sudo_tally_round(1, Some(1)).tallied_projects, 1);
+        challenge(&mut contract);
+
+        testing_env!(at(accounts(0), 150).build());
+        contract.sudo_resolve_challenge((1, accounts(1)), true);
+        let round = contract.get_round(1).unwrap();
+        assert_eq!((round.projects, round.tallied_projects), (1, 0));
+        assert!(contract.sudo_tally_round(1, None).is_tallied());
+    }
+
+    #[test]
+    fn test_disqualify_redistributes_match() {
+        let mut contract = setup();
+        challenge(&mut contract);
+        assert_eq!(contract.get_round(1).unwrap().open_challenges, vec![(1, accounts(1))]);
+
+        testing_env!(at(accounts(0), 150).build());
+        let challenge = contract.sudo_resolve_challenge((1, accounts(1)), true);
+        assert_eq!(challenge.status, ChallengeStatus::Upheld);
+        let project = contract.get_project((1, accounts(1))).unwrap();
+        assert_eq!(project.status, ProjectStatus::Disqualified);
+        let round = contract.get_round(1).unwrap();
+        assert_eq!((round.support_area, round.projects, round.open_challenges.len()), (1, 1, 0));
+
+        testing_env!(at(accounts(0), 250).build());
+        contract.sudo_finish_round(1, None);
+        let other = contract.get_project((1, accounts(2))).unwrap();
+        assert_eq!(contract.grant_for((1, accounts(1)), None), (U128(0), U128(0)));
+        assert_eq!(
+            contract.grant_for((1, accounts(2)), None).1 .0,
+            other.grants.0 + round.support_pool.0
+        );
+
+        let fee_amount = contract.get_fee_amount(None);
+        testing_env!(get_context(accounts(3)).build());
+        contract.claim_grant_refund((1, accounts(1)), None);
+        let refund = U128(project.grants.0 / 2);
+        resolve_with(PromiseResult::Failed);
+        assert_eq!(
+            contract.on_claim_grant_refund((1, accounts(1)), accounts(3), refund, None),
+            U128(0)
+        );
+        testing_env!(get_context(accounts(3)).build());
+        contract.claim_grant_refund((1, accounts(1)), None);
+        resolve_with(PromiseResult::Successful(vec![]));
+        assert_eq!(
+            contract.on_claim_grant_refund((1, accounts(1)), accounts(3), refund, None),
+            refund
+        );
+        let voters = contract.get_project_voters((1, accounts(1)), None, None);
+        assert_eq!(voters.len(), 1);
+        assert_eq!(voters[0].account_id, accounts(4));
+        // The fee was collected when the round finished.
+        assert_eq!(contract.get_fee_amount(None), fee_amount);
+    }
+
+    #[test]
+    fn test_grant_refund_keeps_fee_of_cancelled_round() {
+        let mut contract = setup();
+        challenge(&mut contract);
+        testing_env!(at(accounts(0), 150).build());
+        contract.sudo_resolve_challenge((1, accounts(1)), true);
+        contract.sudo_cancel_round(1);
+        let fees = contract.get_round(1).unwrap().fees.0;
+
+        testing_env!(get_context(accounts(3)).build());
+        contract.claim_grant_refund((1, accounts(1)), None);
+        let fee = contract.get_fee_amount(None).0;
+        assert!(fee > 0);
+        assert_eq!(contract.get_round(1).unwrap().fees.0, fees - fee);
+    }
+
+    #[test]
+    fn test_dismiss_challenge_keeps_bond() {
+        let mut contract = setup();
+        let fee_amount = contract.get_fee_amount(None).0;
+        challenge(&mut contract);
+        testing_env!(at(accounts(0), 150).build());
+        let challenge_status = contract.sudo_resolve_challenge((1, accounts(1)), false).status;
+        assert_eq!(challenge_status, ChallengeStatus::Dismissed);
+        assert_eq!(contract.get_fee_amount(None).0, fee_amount + CHALLENGE_BOND);
+        assert_eq!(contract.get_project((1, accounts(1))).unwrap().status, ProjectStatus::Approved);
+
+        // A dismissed challenge does not stop a new one.
+        challenge(&mut contract);
+        assert_eq!(contract.get_challenge((1, accounts(1))).unwrap().status, ChallengeStatus::Open);
+    }
+
+    #[test]
+    fn test_cancel_returns_open_bonds() {
+        let mut contract = setup();
+        let fee_amount = contract.get_fee_amount(None);
+        challenge(&mut contract);
+        testing_env!(at(accounts(0), 150).build());
+        let round = contract.sudo_cancel_round(1);
+        assert!(round.open_challenges.is_empty());
+        let challenge = contract.get_challenge((1, accounts(1))).unwrap();
+        assert_eq!(challenge.status, ChallengeStatus::Cancelled);
+        assert_eq!(contract.get_fee_amount(None), fee_amount);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_NOT_REVIEW_PHASE")]
+    fn test_challenge_during_voting() {
+        let mut contract = setup();
+        testing_env!(at(accounts(5), 50).attached_deposit(CHALLENGE_BOND).build());
+        contract.challenge_project((1, accounts(1)), "sybil votes".into());
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_OPEN_CHALLENGES")]
+    fn test_finish_with_open_challenge() {
+        let mut contract = setup();
+        challenge(&mut contract);
+        testing_env!(at(accounts(0), 250).build());
+        contract.sudo_finish_round(1, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_PROJECT_NOT_DISQUALIFIED")]
+    fn test_claim_grant_refund_approved_project() {
+        let mut contract = setup();
+        testing_env!(get_context(accounts(3)).build());
+        contract.claim_grant_refund((1, accounts(1)), None);
+    }
+}
@@ -1,12 +1,16 @@
 #[cfg(all(test, not(target_arch = "wasm32")))]
 mod tests {
+    use near_contract_standards::storage_management::StorageManagement;
+    use near_sdk::json_types::{Base58CryptoHash, U128};
     use near_sdk::test_utils::test_env::alice;
-    use near_sdk::test_utils::{accounts, VMContextBuilder};
-    use near_sdk::{testing_env, AccountId};
+    use near_sdk::test_utils::{accounts, get_logs, VMContextBuilder};
+    use near_sdk::{
+        env, testing_env, AccountId, CryptoHash, PromiseResult, RuntimeFeesConfig, VMConfig,
+        ONE_NEAR,
+    };
 
     use grant::*;
-
-    const MINT_STORAGE_COST: u128 = 5870000000000000000000;
+    use std::convert::TryInto;
 
     fn get_context(predecessor_account_id: AccountId) -> VMContextBuilder {
         let mut builder = VMContextBuilder::new();
@@ -23,9 +27,125 @@ mod tests {
         testing_env!(context.build());
         let mut contract = Contract::init();
         assert_eq!(contract.operators().len(), 0);
-        contract.extend_operators(vec![alice().into(), accounts(1).into()]);
-        assert_eq!(contract.operators(), vec![alice(), accounts(1).into()]);
-        contract.remove_operators(vec![accounts(1).into()]);
+        contract.extend_operators(vec![alice(), accounts(1)]);
+        assert_eq!(contract.operators(), vec![alice(), accounts(1)]);
+        contract.remove_operators(vec![accounts(1)]);
         assert_eq!(contract.operators(), vec![alice()]);
     }
+
+    /// Round 1 took a donation of 1 NEAR and finished, which collected its fee.
+    fn setup_fees() -> Contract {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = Contract::init();
+        contract.sudo_new_round(0, 100, None, None);
+        testing_env!(context.attached_deposit(ONE_NEAR).build());
+        contract.storage_deposit(None, None);
+        contract.donate(Some(1));
+        let payout_at = 101 + DEFAULT_REVIEW_DURATION as u64;
+        testing_env!(context
+            .attached_deposit(0)
+            .block_timestamp(payout_at * 1_000_000_000)
+            .build());
+        contract.sudo_finish_round(1, None);
+        testing_env!(context.attached_deposit(1).build());
+        contract.set_treasury(accounts(5));
+        contract
+    }
+
+    fn resolve_with(result: PromiseResult) {
+        testing_env!(
+            get_context(accounts(0)).build(),
+            VMConfig::test(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![result],
+        );
+    }
+
+    #[test]
+    fn test_withdraw_fees() {
+        let mut contract = setup_fees();
+        let fee_amount = contract.get_fee_amount(None);
+        assert_eq!(fee_amount, U128(ONE_NEAR / 20));
+        contract.sudo_withdraw_fees(fee_amount, None, None);
+        assert_eq!(contract.get_fee_amount(None), U128(0));
+
+        resolve_with(PromiseResult::Successful(vec![]));
+        assert_eq!(contract.on_withdraw_fees(fee_amount, accounts(5), None), fee_amount);
+        assert_eq!(contract.get_fee_amount(None), U128(0));
+        assert!(get_logs()[0].starts_with("EVENT_JSON:"));
+        assert!(get_logs()[0].contains("\"event\":\"fees_withdrawn\""));
+    }
+
+    #[test]
+    fn test_withdraw_fees_rollback_on_failed_transfer() {
+        let mut contract = setup_fees();
+        let fee_amount = contract.get_fee_amount(None);
+        contract.sudo_withdraw_fees(U128(fee_amount.0 / 2), None, None);
+
+        resolve_with(PromiseResult::Failed);
+        assert_eq!(contract.on_withdraw_fees(U128(fee_amount.0 / 2), accounts(5), None), U128(0));
+        assert_eq!(contract.get_fee_amount(None), fee_amount);
+        assert!(get_logs().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_TOO_MUCH")]
+    fn test_withdraw_fees_too_much() {
+        let mut contract = setup_fees();
+        let fee_amount = contract.get_fee_amount(None);
+        contract.sudo_withdraw_fees(U128(fee_amount.0 + 1), None, None);
+    }
+
+    #[test]
+    fn test_stage_and_deploy_upgrade() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.attached_deposit(1).build());
+        let mut contract = Contract::init();
+        contract.set_upgrade_delay(100);
+        let code = vec![0, 97, 115, 109];
+        let staged = contract.stage_upgrade(code.clone().into());
+        let code_hash: CryptoHash = env::sha256(&code).try_into().unwrap();
+        assert_eq!(staged.code_hash, Base58CryptoHash::from(code_hash));
+        assert_eq!(staged.deployable_at, 100);
+        assert!(get_logs().last().unwrap().contains("\"event\":\"upgrade_staged\""));
+
+        testing_env!(context.block_timestamp(100 * 1_000_000_000).build());
+        contract.deploy_upgrade();
+        assert!(contract.get_staged_upgrade().is_none());
+        assert!(get_logs().last().unwrap().contains("\"event\":\"upgrade_deployed\""));
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_UPGRADE_DELAYED")]
+    fn test_deploy_upgrade_before_delay() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.attached_deposit(1).build());
+        let mut contract = Contract::init();
+        contract.set_upgrade_delay(100);
+        contract.stage_upgrade(vec![1].into());
+        testing_env!(context.block_timestamp(99 * 1_000_000_000).build());
+        contract.deploy_upgrade();
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_UPGRADE_DELAY_DECREASE")]
+    fn test_lower_upgrade_delay() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.attached_deposit(1).build());
+        let mut contract = Contract::init();
+        contract.set_upgrade_delay(100);
+        contract.set_upgrade_delay(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_NOT_OWNER")]
+    fn test_stage_upgrade_not_owner() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = Contract::init();
+        testing_env!(context.predecessor_account_id(accounts(1)).attached_deposit(1).build());
+        contract.stage_upgrade(vec![1].into());
+    }
 }
@@ -0,0 +1,90 @@
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use near_contract_standards::storage_management::StorageManagement;
+    use near_sdk::serde_json::{self, Value};
+    use near_sdk::test_utils::{accounts, get_logs, VMContextBuilder};
+    use near_sdk::{testing_env, AccountId, ONE_NEAR};
+
+    use grant::*;
+
+    fn get_context(predecessor_account_id: AccountId) -> VMContextBuilder {
+        let mut builder = VMContextBuilder::new();
+        builder
+            .current_account_id(accounts(0))
+            .signer_account_id(predecessor_account_id.clone())
+            .predecessor_account_id(predecessor_account_id);
+        builder
+    }
+
+    fn register_accounts(contract: &mut Contract) {
+        testing_env!(get_context(accounts(0)).attached_deposit(ONE_NEAR).build());
+        for index in 0..6 {
+            contract.storage_deposit(Some(accounts(index)), None);
+        }
+        testing_env!(get_context(accounts(0)).build());
+    }
+
+    fn last_event() -> Value {
+        let logs = get_logs();
+        serde_json::from_str(logs.last().unwrap().strip_prefix("EVENT_JSON:").unwrap()).unwrap()
+    }
+
+    fn amount(value: &Value) -> u128 {
+        value.as_str().unwrap().parse().unwrap()
+    }
+
+    #[test]
+    fn test_events() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = Contract::init();
+        register_accounts(&mut contract);
+        contract.sudo_new_round(0, 100, None, None);
+        let event = &last_event();
+        assert_eq!(event["standard"], "grant");
+        assert_eq!(event["version"], "1.0.0");
+        assert_eq!(event["event"], "round_created");
+        assert_eq!(event["data"]["round"]["id"], 1);
+
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        contract.new_project(Some(1), "a".into(), "b".into(), "c".into(), "d".into());
+        assert_eq!(last_event()["event"], "project_created");
+        assert_eq!(last_event()["data"]["project"]["status"], "Pending");
+        assert!(amount(&last_event()["data"]["storage_cost"]) > 0);
+
+        testing_env!(context.predecessor_account_id(accounts(0)).build());
+        contract.sudo_approve_project((1, accounts(1)), Some("ok".into()));
+        let event = &last_event();
+        assert_eq!(event["event"], "project_reviewed");
+        assert_eq!(event["data"]["status"], "Approved");
+        assert_eq!(event["data"]["reason"], "ok");
+
+        testing_env!(context
+            .predecessor_account_id(accounts(2))
+            .attached_deposit(ONE_NEAR)
+            .build());
+        contract.vote((1, accounts(1)), 2);
+        let event = &last_event();
+        assert_eq!(event["event"], "vote");
+        let data = &event["data"];
+        assert_eq!(data["votes"], 2);
+        assert_eq!(amount(&data["cost"]), 3 * ONE_NEAR / 10);
+        assert_eq!(amount(&data["platform_fee"]), 3 * ONE_NEAR / 10 / 20);
+        assert!(amount(&data["storage_cost"]) > 0);
+        assert_eq!(amount(&data["cost"]) + amount(&data["refund"]), ONE_NEAR);
+        assert_eq!(data["token_id"], Value::Null);
+
+        contract.donate(Some(1));
+        let data = &last_event()["data"];
+        assert_eq!(amount(&data["amount"]), ONE_NEAR);
+        assert_eq!(amount(&data["platform_fee"]), ONE_NEAR / 20);
+        assert!(amount(&data["storage_cost"]) > 0);
+
+        testing_env!(context.predecessor_account_id(accounts(0)).attached_deposit(0).build());
+        contract.extend_operators(vec![accounts(3)]);
+        assert_eq!(last_event()["event"], "operators_added");
+        contract.sudo_config(Some(100), None, None, None);
+        assert_eq!(last_event()["event"], "config_changed");
+        assert_eq!(last_event()["data"]["fee_point"], 100);
+    }
+}
@@ -0,0 +1,287 @@
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use near_contract_standards::storage_management::StorageManagement;
+    use near_sdk::test_utils::{accounts, VMContextBuilder};
+    use near_sdk::{testing_env, AccountId, ONE_NEAR};
+
+    use grant::*;
+
+    /// First second of payouts of rounds ending at 100 after the default review.
+    const PAYOUT_AT: u64 = 101 + DEFAULT_REVIEW_DURATION as u64;
+
+    fn get_context(predecessor_account_id: AccountId) -> VMContextBuilder {
+        let mut builder = VMContextBuilder::new();
+        builder
+            .current_account_id(accounts(0))
+            .signer_account_id(predecessor_account_id.clone())
+            .predecessor_account_id(predecessor_account_id);
+        builder
+    }
+
+    fn register_accounts(contract: &mut Contract) {
+        testing_env!(get_context(accounts(0)).attached_deposit(ONE_NEAR).build());
+        for index in 0..6 {
+            contract.storage_deposit(Some(accounts(index)), None);
+        }
+        testing_env!(get_context(accounts(0)).build());
+    }
+
+    fn approve_projects(contract: &mut Contract, owners: Vec<AccountId>) {
+        testing_env!(get_context(accounts(0)).build());
+        for owner in owners {
+            contract.sudo_approve_project((1, owner), None);
+        }
+    }
+
+    /// Project of accounts(1) gets 1 and 3 votes, project of accounts(2) gets 2 and 2 votes.
+    fn setup_round(matching: MatchingStrategy) -> (Contract, u128) {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = Contract::init();
+        register_accounts(&mut contract);
+        contract.sudo_new_round(0, 100, Some(matching), None);
+
+        for owner in [accounts(1), accounts(2)] {
+            testing_env!(context.predecessor_account_id(owner).build());
+            contract.new_project(Some(1), "a".into(), "b".into(), "c".into(), "d".into());
+        }
+        approve_projects(&mut contract, vec![accounts(1), accounts(2)]);
+
+        testing_env!(context
+            .predecessor_account_id(accounts(3))
+            .attached_deposit(ONE_NEAR)
+            .build());
+        contract.donate(Some(1));
+        contract.vote((1, accounts(1)), 1);
+        contract.vote((1, accounts(2)), 2);
+        testing_env!(context.predecessor_account_id(accounts(4)).build());
+        contract.vote((1, accounts(1)), 3);
+        contract.vote((1, accounts(2)), 2);
+
+        testing_env!(context
+            .attached_deposit(0)
+            .block_timestamp(PAYOUT_AT * 1_000_000_000)
+            .build());
+        let pool = contract.get_round(1).unwrap().support_pool.0;
+        (contract, pool)
+    }
+
+    fn matched(contract: &Contract, owner: AccountId) -> u128 {
+        let project = contract.get_project((1, owner.clone())).unwrap();
+        contract.grant_for((1, owner), None).1 .0 - project.grants.0
+    }
+
+    fn assert_close(actual: u128, expected: u128) {
+        assert!(actual.abs_diff(expected) * 1_000_000_000 < expected, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn test_pairwise_matching() {
+        let (contract, pool) = setup_round(MatchingStrategy::Pairwise);
+        assert_eq!(contract.get_round(1).unwrap().support_area, 7);
+        assert_eq!(matched(&contract, accounts(1)), pool * 3 / 7);
+        assert_eq!(matched(&contract, accounts(2)), pool * 4 / 7);
+    }
+
+    #[test]
+    fn test_clr_matching() {
+        let (contract, pool) = setup_round(MatchingStrategy::Clr);
+        // Contributions are paid weights: 1 and 6 for the first project, 3 and 3 for the second.
+        let first = (1f64 + 6f64.sqrt()).powi(2) - 7f64;
+        let second = (2f64 * 3f64.sqrt()).powi(2) - 6f64;
+        let expected = pool as f64 * first / (first + second);
+        let first_match = matched(&contract, accounts(1));
+        assert!((first_match as f64 - expected).abs() / expected < 1e-6);
+        assert!(first_match + matched(&contract, accounts(2)) <= pool);
+        assert!(first_match > pool * 3 / 7);
+    }
+
+    #[test]
+    fn test_clr_single_voter_gets_no_match() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = Contract::init();
+        register_accounts(&mut contract);
+        contract.sudo_new_round(0, 100, Some(MatchingStrategy::Clr), None);
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        contract.new_project(Some(1), "a".into(), "b".into(), "c".into(), "d".into());
+        approve_projects(&mut contract, vec![accounts(1)]);
+        testing_env!(context
+            .predecessor_account_id(accounts(3))
+            .attached_deposit(10 * ONE_NEAR)
+            .build());
+        contract.vote((1, accounts(1)), 2);
+        contract.vote((1, accounts(1)), 3);
+        assert_eq!(contract.get_project((1, accounts(1))).unwrap().match_area.0, 0);
+    }
+
+    /// Accounts 3 and 4 co-vote for two projects, accounts 5 and 2 vote for one.
+    fn setup_bounded_round(matching: MatchingStrategy) -> (Contract, u128) {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = Contract::init();
+        register_accounts(&mut contract);
+        contract.sudo_new_round(0, 100, Some(matching), None);
+
+        for owner in [accounts(0), accounts(1), accounts(2)] {
+            testing_env!(context.predecessor_account_id(owner).build());
+            contract.new_project(Some(1), "a".into(), "b".into(), "c".into(), "d".into());
+        }
+        approve_projects(&mut contract, vec![accounts(0), accounts(1), accounts(2)]);
+        testing_env!(context.attached_deposit(ONE_NEAR).build());
+        contract.donate(Some(1));
+        for voter in [accounts(3), accounts(4)] {
+            testing_env!(context.predecessor_account_id(voter).build());
+            contract.vote((1, accounts(0)), 1);
+            contract.vote((1, accounts(1)), 1);
+        }
+        for voter in [accounts(5), accounts(2)] {
+            testing_env!(context.predecessor_account_id(voter).build());
+            contract.vote((1, accounts(2)), 1);
+        }
+
+        testing_env!(context
+            .predecessor_account_id(accounts(0))
+            .attached_deposit(0)
+            .block_timestamp(PAYOUT_AT * 1_000_000_000)
+            .build());
+        let pool = contract.get_round(1).unwrap().support_pool.0;
+        (contract, pool)
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_VOTING_NOT_ENDED")]
+    fn test_tally_while_voting() {
+        let (mut contract, _) = setup_bounded_round(MatchingStrategy::PairwiseBounded { m: 1 });
+        testing_env!(get_context(accounts(0)).block_timestamp(50 * 1_000_000_000).build());
+        contract.sudo_tally_round(1, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_VOTING_ENDED")]
+    fn test_extend_tallied_round() {
+        let (mut contract, _) = setup_bounded_round(MatchingStrategy::PairwiseBounded { m: 1 });
+        contract.sudo_tally_round(1, Some(1));
+        contract.sudo_update_round(1, true, None, Some(PAYOUT_AT as u32 + 100));
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_TOO_MANY_VOTERS")]
+    fn test_pairwise_bounded_max_voters() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = Contract::init();
+        register_accounts(&mut contract);
+        contract.sudo_new_round(
+            0,
+            100,
+            Some(MatchingStrategy::PairwiseBounded { m: 1 }),
+            Some(RoundParams { max_voters_per_project: Some(2), ..Default::default() }),
+        );
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        contract.new_project(Some(1), "a".into(), "b".into(), "c".into(), "d".into());
+        approve_projects(&mut contract, vec![accounts(1)]);
+        for voter in [accounts(3), accounts(4), accounts(3)] {
+            testing_env!(context.predecessor_account_id(voter).attached_deposit(ONE_NEAR).build());
+            contract.vote((1, accounts(1)), 1);
+        }
+        testing_env!(context.predecessor_account_id(accounts(5)).build());
+        contract.vote((1, accounts(1)), 1);
+    }
+
+    #[test]
+    fn test_pairwise_bounded_matching() {
+        let (mut contract, pool) = setup_bounded_round(MatchingStrategy::PairwiseBounded { m: 1 });
+        assert_eq!(matched(&contract, accounts(2)), 0);
+
+        assert_eq!(contract.sudo_tally_round(1, Some(2)).tallied_projects, 2);
+        assert!(!contract.get_round(1).unwrap().is_tallied());
+        assert_eq!(matched(&contract, accounts(2)), 0);
+        assert!(contract.sudo_tally_round(1, Some(2)).is_tallied());
+
+        // The colluding pair shares k = 2 across two projects, the other pair has k = 1:
+        // areas are 1/3, 1/3 and 1/2 of a single pair.
+        assert_close(matched(&contract, accounts(0)), pool * 2 / 7);
+        assert_close(matched(&contract, accounts(1)), pool * 2 / 7);
+        assert_close(matched(&contract, accounts(2)), pool * 3 / 7);
+        contract.sudo_finish_round(1, None);
+    }
+
+    #[test]
+    fn test_pairwise_matching_rewards_colluding_pair() {
+        let (contract, pool) = setup_bounded_round(MatchingStrategy::Pairwise);
+        assert_eq!(matched(&contract, accounts(0)), pool / 3);
+        assert_eq!(matched(&contract, accounts(2)), pool / 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_ROUND_NOT_TALLIED")]
+    fn test_pairwise_bounded_finish_requires_tally() {
+        let (mut contract, _) = setup_bounded_round(MatchingStrategy::PairwiseBounded { m: 1 });
+        contract.sudo_finish_round(1, None);
+    }
+
+    #[test]
+    fn test_pairwise_bounded_tally_skips_unapproved() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = Contract::init();
+        register_accounts(&mut contract);
+        contract.sudo_new_round(0, 100, Some(MatchingStrategy::PairwiseBounded { m: 1 }), None);
+        for owner in [accounts(0), accounts(1), accounts(2)] {
+            testing_env!(context.predecessor_account_id(owner).build());
+            contract.new_project(Some(1), "a".into(), "b".into(), "c".into(), "d".into());
+        }
+        approve_projects(&mut contract, vec![accounts(0), accounts(2)]);
+        testing_env!(context
+            .predecessor_account_id(accounts(0))
+            .block_timestamp(PAYOUT_AT * 1_000_000_000)
+            .build());
+
+        let round = contract.sudo_tally_round(1, Some(2));
+        assert_eq!((round.tallied_projects, round.tally_cursor), (1, 2));
+        assert!(!round.is_tallied());
+        let round = contract.sudo_tally_round(1, None);
+        assert_eq!((round.tallied_projects, round.tally_cursor), (2, 3));
+        assert!(round.is_tallied());
+    }
+
+    #[test]
+    fn test_pairwise_bounded_tally_pages_voters_of_project() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = Contract::init();
+        register_accounts(&mut contract);
+        contract.sudo_new_round(0, 100, Some(MatchingStrategy::PairwiseBounded { m: 1 }), None);
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        contract.new_project(Some(1), "a".into(), "b".into(), "c".into(), "d".into());
+        approve_projects(&mut contract, vec![accounts(1)]);
+
+        let voters: u32 = 50;
+        for index in 0..voters {
+            let voter: AccountId = format!("voter{}.near", index).parse().unwrap();
+            testing_env!(context.predecessor_account_id(voter).attached_deposit(ONE_NEAR).build());
+            contract.storage_deposit(None, None);
+            contract.vote((1, accounts(1)), 1);
+        }
+
+        context.predecessor_account_id(accounts(0)).attached_deposit(0);
+        context.block_timestamp(PAYOUT_AT * 1_000_000_000);
+        let pairs = voters * (voters - 1) / 2;
+        let calls = pairs.div_ceil(TALLY_PAIRS_PER_CALL);
+        for _ in 1..calls {
+            testing_env!(context.build());
+            let round = contract.sudo_tally_round(1, Some(1));
+            assert!(!round.is_tallied());
+            assert_eq!(round.tally_cursor, 0);
+        }
+        testing_env!(context.build());
+        let round = contract.sudo_tally_round(1, Some(1));
+        assert!(round.is_tallied());
+        assert_eq!(round.tally_cursor, 1);
+        // Every pair of single votes has k = 1, so each adds half of m.
+        let project = contract.get_project((1, accounts(1))).unwrap();
+        assert_eq!(project.match_area.0, pairs as u128 * 1_000_000_000_000 / 2);
+        contract.sudo_finish_round(1, None);
+    }
+}
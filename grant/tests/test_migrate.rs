@@ -0,0 +1,242 @@
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use std::collections::HashMap;
+
+    use near_contract_standards::storage_management::StorageManagement;
+    use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+    use near_sdk::collections::{TreeMap, UnorderedMap, UnorderedSet};
+    use near_sdk::json_types::U128;
+    use near_sdk::serde_json;
+    use near_sdk::test_utils::{accounts, VMContextBuilder};
+    use near_sdk::{env, testing_env, AccountId, ONE_NEAR};
+
+    use grant::*;
+
+    fn get_context(predecessor_account_id: AccountId) -> VMContextBuilder {
+        let mut builder = VMContextBuilder::new();
+        builder
+            .current_account_id(accounts(0))
+            .signer_account_id(predecessor_account_id.clone())
+            .predecessor_account_id(predecessor_account_id);
+        builder
+    }
+
+    /// State of the first release with one round, a project of accounts(1)
+    /// and 2 votes of accounts(2) for it.
+    fn write_v1_state() {
+        let project_id = (1, accounts(1));
+        let mut rounds = TreeMap::new(StorageKey::Rounds);
+        rounds.insert(
+            &1,
+            &RoundV1 {
+                created_at: 0,
+                end_at: 100,
+                pure_support_pool: U128(ONE_NEAR),
+                id: 1,
+                start_at: 0,
+                support_area: 0,
+                support_pool: U128(ONE_NEAR),
+                vote_cost: U128(ONE_NEAR / 10),
+                projects: 1,
+                status: RoundStatus::Active,
+            },
+        );
+        let mut projects = TreeMap::new(StorageKey::Projects);
+        projects.insert(
+            &project_id,
+            &ProjectV1 {
+                name: "a".into(),
+                description: "b".into(),
+                external_url: "c".into(),
+                image: "d".into(),
+                round_id: 1,
+                owner: accounts(1),
+                created_at: 0,
+                total_votes: 2,
+                grants: U128(ONE_NEAR / 10 * 3),
+                support_area: 0,
+                withdrawn: U128(0),
+            },
+        );
+        let mut round_projects = UnorderedMap::new(StorageKey::RoundProjects);
+        let mut round_set = UnorderedSet::new(StorageKey::ProjectsPerRound { round_id: 1 });
+        round_set.insert(&project_id);
+        round_projects.insert(&1, &round_set);
+        let mut rounds_for_owner = UnorderedMap::new(StorageKey::RoundsForOwner);
+        let mut owner_rounds =
+            UnorderedSet::new(StorageKey::AccountRounds { account_id: accounts(1) });
+        owner_rounds.insert(&1);
+        rounds_for_owner.insert(&accounts(1), &owner_rounds);
+        let mut votes = UnorderedMap::new(StorageKey::Votes);
+        votes.insert(&accounts(2), &HashMap::from([(project_id.clone(), 2)]));
+        let mut grants = UnorderedMap::new(StorageKey::Grants);
+        grants.insert(&accounts(2), &HashMap::from([(project_id, U128(ONE_NEAR / 10 * 3))]));
+
+        env::state_write(&ContractV1 {
+            owner_id: accounts(0),
+            operators: UnorderedSet::new(StorageKey::Operators),
+            projects,
+            rounds_for_owner,
+            grants,
+            votes,
+            round_projects,
+            rounds,
+            current_round_id: 1,
+            fee_point: 300,
+            fee_amount: U128(7),
+            default_duration: 1000,
+            default_vote_cost: U128(ONE_NEAR / 10),
+        });
+    }
+
+    #[test]
+    fn test_migrate_v1() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        write_v1_state();
+        assert_eq!(state_version(), StateVersion::V1);
+
+        let mut contract = Contract::migrate();
+        assert_eq!(state_version(), StateVersion::V2);
+        assert!(!contract.is_migrated());
+        assert!(contract.sudo_migrate(None));
+        assert!(contract.is_migrated());
+        let config = serde_json::to_value(contract.get_config()).unwrap();
+        assert_eq!(config["owner_id"], accounts(0).to_string());
+        assert_eq!(config["treasury_id"], accounts(0).to_string());
+        assert_eq!(config["last_round_id"], 1);
+        assert_eq!(config["fee_point"], 300);
+        assert_eq!(config["fee_amount"], "7");
+
+        let round = contract.get_round(1).unwrap();
+        assert_eq!(round.support_pool, U128(ONE_NEAR));
+        assert_eq!(round.projects, 1);
+        assert_eq!(round.matching, MatchingStrategy::Pairwise);
+        assert!(round.accepted_tokens.is_empty());
+        let project = contract.get_project((1, accounts(1))).unwrap();
+        assert_eq!(project.total_votes, 2);
+        assert_eq!(project.beneficiary, None);
+        assert_eq!(contract.list_projects(1, None, None, Some(ProjectStatus::Approved)).len(), 1);
+        assert_eq!(contract.projects_for_owner(accounts(1), None, None).len(), 1);
+        let page = contract.list_projects_v2(1, ProjectSort::TotalVotes, None, None);
+        assert_eq!(page.projects.len(), 1);
+        let contributions = contract.get_account_contributions(accounts(2), 1, None, None);
+        assert_eq!(contributions[0].votes, 2);
+        assert_eq!(contributions[0].grants, U128(ONE_NEAR / 10 * 3));
+        assert_eq!(contract.get_current_round().unwrap().id, 1);
+
+        // Old votes keep counting for the support area of new ones.
+        testing_env!(context
+            .predecessor_account_id(accounts(3))
+            .attached_deposit(ONE_NEAR)
+            .build());
+        contract.storage_deposit(None, None);
+        contract.vote((1, accounts(1)), 1);
+        assert_eq!(contract.donate(None).id, 1);
+        let project = contract.get_project((1, accounts(1))).unwrap();
+        assert_eq!(project.total_votes, 3);
+        assert_eq!(project.support_area, 2);
+        assert_eq!(contract.get_round(1).unwrap().support_area, 2);
+        let page = contract.list_projects_v2(1, ProjectSort::TotalVotes, None, None);
+        assert_eq!(page.projects[0].total_votes, 3);
+    }
+
+    #[test]
+    fn test_migrate_v1_in_pages() {
+        testing_env!(get_context(accounts(0)).build());
+        write_v1_state();
+        let mut contract = Contract::migrate();
+        // The round, the project, the voter and the calendar, the current round is checked
+        // on its own first.
+        let mut calls = 1;
+        while !contract.sudo_migrate(Some(1)) {
+            calls += 1;
+        }
+        assert_eq!(calls, 5);
+        assert_eq!(contract.get_project((1, accounts(1))).unwrap().total_votes, 2);
+        assert_eq!(contract.get_account_contributions(accounts(2), 1, None, None)[0].votes, 2);
+        assert_eq!(contract.get_current_round().unwrap().id, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_MIGRATION_PENDING")]
+    fn test_vote_while_migrating() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        write_v1_state();
+        let mut contract = Contract::migrate();
+        assert!(!contract.sudo_migrate(Some(2)));
+        testing_env!(context
+            .predecessor_account_id(accounts(3))
+            .attached_deposit(ONE_NEAR)
+            .build());
+        contract.vote((1, accounts(1)), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_MIGRATION_PENDING")]
+    fn test_new_round_while_migrating() {
+        testing_env!(get_context(accounts(0)).build());
+        write_v1_state();
+        let mut contract = Contract::migrate();
+        assert!(!contract.sudo_migrate(Some(1)));
+        contract.sudo_new_round(200, 300, None, None);
+    }
+
+    #[test]
+    fn test_migrate_skips_versioned_rounds() {
+        testing_env!(get_context(accounts(0)).build());
+        write_v1_state();
+        let mut state: ContractV1 = env::state_read().unwrap();
+        let mut rounds =
+            TreeMap::<RoundId, VersionedRound>::try_from_slice(&state.rounds.try_to_vec().unwrap())
+                .unwrap();
+        let mut round = state.rounds.get(&1).unwrap();
+        round.id = 2;
+        rounds.insert(&2, &VersionedRound::V1(round));
+        state.rounds = TreeMap::try_from_slice(&rounds.try_to_vec().unwrap()).unwrap();
+        env::state_write(&state);
+
+        let mut contract = Contract::migrate();
+        assert!(contract.sudo_migrate(None));
+        assert_eq!(contract.get_round(1).unwrap().id, 1);
+        assert_eq!(contract.get_round(2).unwrap().id, 2);
+    }
+
+    #[test]
+    fn test_rollover_round_finished_before_upgrade() {
+        testing_env!(get_context(accounts(0)).build());
+        write_v1_state();
+        let mut state: ContractV1 = env::state_read().unwrap();
+        let mut round = state.rounds.get(&1).unwrap();
+        round.status = RoundStatus::Finished;
+        round.support_area = 2;
+        state.rounds.insert(&1, &round);
+        let mut project = state.projects.get(&(1, accounts(1))).unwrap();
+        project.support_area = 2;
+        state.projects.insert(&(1, accounts(1)), &project);
+        env::state_write(&state);
+
+        let mut contract = Contract::migrate();
+        assert!(contract.sudo_migrate(None));
+        assert_eq!(contract.get_round(1).unwrap().matched.0, ONE_NEAR);
+        let round = contract.sudo_rollover_round(1, RolloverTarget::Reserve);
+        assert_eq!(round.rollover.unwrap().amount.0, 0);
+    }
+
+    #[test]
+    fn test_migrate_current_is_noop() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = Contract::init();
+        contract.sudo_new_round(0, 100, Some(MatchingStrategy::Clr), None);
+        assert_eq!(state_version(), StateVersion::V2);
+        env::state_write(&contract);
+
+        let contract = Contract::migrate();
+        assert_eq!(state_version(), StateVersion::V2);
+        assert!(contract.is_migrated());
+        assert_eq!(contract.get_round(1).unwrap().matching, MatchingStrategy::Clr);
+        assert_eq!(serde_json::to_value(contract.get_config()).unwrap()["last_round_id"], 1);
+    }
+}
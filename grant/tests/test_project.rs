@@ -0,0 +1,280 @@
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use near_contract_standards::storage_management::StorageManagement;
+    use near_sdk::json_types::U128;
+    use near_sdk::test_utils::{accounts, VMContextBuilder};
+    use near_sdk::{testing_env, AccountId, PromiseResult, RuntimeFeesConfig, VMConfig, ONE_NEAR};
+
+    use grant::*;
+
+    /// First second of payouts of rounds ending at 100 after the default review.
+    const PAYOUT_AT: u64 = 101 + DEFAULT_REVIEW_DURATION as u64;
+
+    fn get_context(predecessor_account_id: AccountId) -> VMContextBuilder {
+        let mut builder = VMContextBuilder::new();
+        builder
+            .current_account_id(accounts(0))
+            .signer_account_id(predecessor_account_id.clone())
+            .predecessor_account_id(predecessor_account_id);
+        builder
+    }
+
+    fn register_accounts(contract: &mut Contract) {
+        testing_env!(get_context(accounts(0)).attached_deposit(ONE_NEAR).build());
+        for index in 0..6 {
+            contract.storage_deposit(Some(accounts(index)), None);
+        }
+        testing_env!(get_context(accounts(0)).build());
+    }
+
+    fn setup_finished_round() -> (Contract, ProjectId) {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = Contract::init();
+        register_accounts(&mut contract);
+        contract.sudo_new_round(0, 100, None, None);
+
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        contract.new_project(Some(1), "a".into(), "b".into(), "c".into(), "d".into());
+        let project_id: ProjectId = (1, accounts(1));
+        testing_env!(context.predecessor_account_id(accounts(0)).build());
+        contract.sudo_approve_project(project_id.clone(), None);
+
+        testing_env!(context
+            .predecessor_account_id(accounts(2))
+            .attached_deposit(10 * ONE_NEAR)
+            .build());
+        contract.vote(project_id.clone(), 3);
+
+        testing_env!(context
+            .predecessor_account_id(accounts(0))
+            .attached_deposit(0)
+            .block_timestamp(PAYOUT_AT * 1_000_000_000)
+            .build());
+        contract.sudo_finish_round(1, None);
+
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        (contract, project_id)
+    }
+
+    #[test]
+    fn test_withdraw_rollback_on_failed_transfer() {
+        let (mut contract, project_id) = setup_finished_round();
+        let (withdrawable, _) = contract.grant_for(project_id.clone(), None);
+        assert!(withdrawable.0 > 0);
+
+        contract.withdraw(project_id.clone(), withdrawable, None);
+        assert_eq!(contract.get_project(project_id.clone()).unwrap().withdrawn, withdrawable);
+
+        testing_env!(
+            get_context(accounts(0)).build(),
+            VMConfig::test(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![PromiseResult::Failed],
+        );
+        assert_eq!(
+            contract.on_withdraw(project_id.clone(), accounts(1), withdrawable, None),
+            U128(0)
+        );
+        assert_eq!(contract.get_project(project_id.clone()).unwrap().withdrawn, U128(0));
+        assert_eq!(contract.grant_for(project_id, None).0, withdrawable);
+    }
+
+    #[test]
+    fn test_withdraw_resolved() {
+        let (mut contract, project_id) = setup_finished_round();
+        let (withdrawable, _) = contract.grant_for(project_id.clone(), None);
+        contract.withdraw(project_id.clone(), withdrawable, None);
+
+        testing_env!(
+            get_context(accounts(0)).build(),
+            VMConfig::test(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![PromiseResult::Successful(vec![])],
+        );
+        assert_eq!(
+            contract.on_withdraw(project_id.clone(), accounts(1), withdrawable, None),
+            withdrawable
+        );
+        assert_eq!(contract.grant_for(project_id, None).0, U128(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_NOT_ALLOWED")]
+    fn test_withdraw_not_allowed() {
+        let (mut contract, project_id) = setup_finished_round();
+        testing_env!(get_context(accounts(3)).build());
+        contract.withdraw(project_id, U128(1), None);
+    }
+
+    #[test]
+    fn test_withdraw_by_delegate_to_beneficiary() {
+        let (mut contract, project_id) = setup_finished_round();
+        testing_env!(get_context(accounts(1)).attached_deposit(1).build());
+        contract.set_project_delegates(project_id.clone(), vec![accounts(3)]);
+        let project = contract.set_project_beneficiary(project_id.clone(), Some(accounts(4)));
+        assert_eq!(project.payout_account(), accounts(4));
+
+        testing_env!(get_context(accounts(3)).build());
+        contract.withdraw(project_id.clone(), U128(1), None);
+        assert_eq!(contract.get_project(project_id).unwrap().withdrawn, U128(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_NOT_PROJECT_OWNER")]
+    fn test_set_beneficiary_not_owner() {
+        let (mut contract, project_id) = setup_finished_round();
+        testing_env!(get_context(accounts(3)).attached_deposit(1).build());
+        contract.set_project_beneficiary(project_id, Some(accounts(3)));
+    }
+
+    #[test]
+    fn test_update_project_keeps_revisions() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = Contract::init();
+        register_accounts(&mut contract);
+        contract.sudo_new_round(0, 100, None, None);
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        contract.new_project(Some(1), "a".into(), "b".into(), "c".into(), "d".into());
+        let project_id: ProjectId = (1, accounts(1));
+
+        testing_env!(context.attached_deposit(1).block_timestamp(10 * 1_000_000_000).build());
+        let project =
+            contract.update_project(project_id.clone(), Some("a2".into()), None, None, None);
+        assert_eq!(project.name, "a2");
+        assert_eq!(project.description, "b");
+        for index in 0..MAX_PROJECT_REVISIONS {
+            contract.update_project(
+                project_id.clone(),
+                Some(format!("a{}", index + 3)),
+                None,
+                None,
+                None,
+            );
+        }
+
+        let revisions = contract.get_project_revisions(project_id.clone());
+        assert_eq!(revisions.len(), MAX_PROJECT_REVISIONS);
+        assert_eq!(revisions[0].name, "a2");
+        assert_eq!(revisions[0].replaced_at, 10);
+        assert_eq!(revisions.last().unwrap().name, format!("a{}", MAX_PROJECT_REVISIONS + 1));
+        assert_eq!(
+            contract.get_project(project_id).unwrap().name,
+            format!("a{}", MAX_PROJECT_REVISIONS + 2)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_ROUND_NOT_ACTIVE")]
+    fn test_update_project_after_round() {
+        let (mut contract, project_id) = setup_finished_round();
+        testing_env!(get_context(accounts(1)).attached_deposit(1).build());
+        contract.update_project(project_id, Some("x".into()), None, None, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_NOT_PROJECT_OWNER")]
+    fn test_update_project_not_owner() {
+        let (mut contract, project_id) = setup_finished_round();
+        testing_env!(get_context(accounts(3)).attached_deposit(1).build());
+        contract.update_project(project_id, Some("x".into()), None, None, None);
+    }
+
+    fn setup_applications() -> Contract {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = Contract::init();
+        register_accounts(&mut contract);
+        contract.sudo_new_round(0, 100, None, None);
+        for owner in [accounts(1), accounts(2), accounts(3)] {
+            testing_env!(context.predecessor_account_id(owner).build());
+            contract.new_project(Some(1), "a".into(), "b".into(), "c".into(), "d".into());
+        }
+        testing_env!(context.predecessor_account_id(accounts(0)).build());
+        contract
+    }
+
+    #[test]
+    fn test_review_projects() {
+        let mut contract = setup_applications();
+        assert_eq!(contract.get_round(1).unwrap().projects, 0);
+        contract.extend_operators(vec![accounts(4)]);
+        testing_env!(get_context(accounts(4)).build());
+        contract.sudo_approve_project((1, accounts(1)), None);
+        let rejected = contract.sudo_reject_project((1, accounts(2)), "spam".into());
+        assert_eq!(rejected.status, ProjectStatus::Rejected);
+        assert_eq!(rejected.review_reason, Some("spam".into()));
+        assert_eq!(contract.get_round(1).unwrap().projects, 1);
+
+        let owners = |status| {
+            contract
+                .list_projects(1, None, None, Some(status))
+                .into_iter()
+                .map(|project| project.owner)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(owners(ProjectStatus::Approved), vec![accounts(1)]);
+        assert_eq!(owners(ProjectStatus::Rejected), vec![accounts(2)]);
+        assert_eq!(owners(ProjectStatus::Pending), vec![accounts(3)]);
+        assert_eq!(contract.list_projects(1, None, None, None).len(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_PROJECT_NOT_APPROVED")]
+    fn test_vote_for_pending_project() {
+        let mut contract = setup_applications();
+        testing_env!(get_context(accounts(4)).attached_deposit(ONE_NEAR).build());
+        contract.vote((1, accounts(1)), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_PROJECT_REVIEWED")]
+    fn test_review_project_twice() {
+        let mut contract = setup_applications();
+        contract.sudo_reject_project((1, accounts(1)), "spam".into());
+        contract.sudo_approve_project((1, accounts(1)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_NOT_OWNER_OR_OPERATOR")]
+    fn test_approve_project_not_operator() {
+        let mut contract = setup_applications();
+        testing_env!(get_context(accounts(1)).build());
+        contract.sudo_approve_project((1, accounts(1)), None);
+    }
+
+    #[test]
+    fn test_list_projects_v2() {
+        let mut contract = setup_applications();
+        for owner in [accounts(1), accounts(2), accounts(3)] {
+            contract.sudo_approve_project((1, owner), None);
+        }
+        testing_env!(get_context(accounts(4)).attached_deposit(ONE_NEAR).build());
+        contract.vote((1, accounts(2)), 3);
+        contract.vote((1, accounts(1)), 1);
+        testing_env!(get_context(accounts(5)).attached_deposit(ONE_NEAR).build());
+        contract.vote((1, accounts(1)), 1);
+
+        let owners = |page: &ProjectPage| {
+            page.projects.iter().map(|project| project.owner.clone()).collect::<Vec<_>>()
+        };
+        let page = contract.list_projects_v2(1, ProjectSort::TotalVotes, None, Some(2));
+        assert_eq!(owners(&page), vec![accounts(2), accounts(1)]);
+        assert_eq!(page.next_key, Some((U128(2), accounts(1))));
+        let page = contract.list_projects_v2(1, ProjectSort::TotalVotes, page.next_key, Some(2));
+        assert_eq!(owners(&page), vec![accounts(3)]);
+        assert_eq!(page.next_key, None);
+
+        let page = contract.list_projects_v2(1, ProjectSort::EstimatedMatch, None, Some(1));
+        assert_eq!(owners(&page), vec![accounts(1)]);
+        let page = contract.list_projects_v2(1, ProjectSort::Grants, None, None);
+        assert_eq!(owners(&page), vec![accounts(2), accounts(1), accounts(3)]);
+        assert_eq!(
+            contract.list_projects_v2(1, ProjectSort::CreatedAt, None, None).projects.len(),
+            3
+        );
+    }
+}
@@ -0,0 +1,171 @@
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use near_contract_standards::fungible_token::receiver::FungibleTokenReceiver;
+    use near_contract_standards::storage_management::StorageManagement;
+    use near_sdk::json_types::U128;
+    use near_sdk::mock::VmAction;
+    use near_sdk::serde_json;
+    use near_sdk::test_utils::{accounts, get_created_receipts, get_logs, VMContextBuilder};
+    use near_sdk::{testing_env, AccountId, PromiseResult, RuntimeFeesConfig, VMConfig, ONE_NEAR};
+
+    use grant::*;
+
+    /// First second of payouts of rounds ending at 100 after the default review.
+    const PAYOUT_AT: u64 = 101 + DEFAULT_REVIEW_DURATION as u64;
+
+    fn get_context(predecessor_account_id: AccountId) -> VMContextBuilder {
+        let mut builder = VMContextBuilder::new();
+        builder
+            .current_account_id(accounts(0))
+            .signer_account_id(predecessor_account_id.clone())
+            .predecessor_account_id(predecessor_account_id);
+        builder
+    }
+
+    fn register_accounts(contract: &mut Contract) {
+        testing_env!(get_context(accounts(0)).attached_deposit(ONE_NEAR).build());
+        for index in 0..6 {
+            contract.storage_deposit(Some(accounts(index)), None);
+        }
+        testing_env!(get_context(accounts(0)).build());
+    }
+
+    fn token() -> AccountId {
+        "token.near".parse().unwrap()
+    }
+
+    /// accounts(2) votes 2 for the project of accounts(1) in NEAR and in tokens,
+    /// accounts(3) donates 1 NEAR.
+    fn setup() -> Contract {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = Contract::init();
+        register_accounts(&mut contract);
+        contract.sudo_new_round(0, 100, None, None);
+        contract.sudo_set_round_token(1, token(), Some(U128(10)));
+        testing_env!(get_context(accounts(1)).build());
+        contract.new_project(Some(1), "a".into(), "b".into(), "c".into(), "d".into());
+        testing_env!(get_context(accounts(0)).build());
+        contract.sudo_approve_project((1, accounts(1)), None);
+
+        testing_env!(get_context(accounts(2)).attached_deposit(ONE_NEAR).build());
+        contract.vote((1, accounts(1)), 2);
+        testing_env!(get_context(token()).build());
+        let msg =
+            serde_json::to_string(&FtMessage::Vote { project_id: (1, accounts(1)), votes: 1 })
+                .unwrap();
+        contract.ft_on_transfer(accounts(2), U128(100), msg);
+        testing_env!(get_context(accounts(3)).attached_deposit(ONE_NEAR).build());
+        contract.donate(Some(1));
+        testing_env!(get_context(accounts(0)).build());
+        contract
+    }
+
+    fn resolve_with(result: PromiseResult) {
+        testing_env!(
+            get_context(accounts(0)).build(),
+            VMConfig::test(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![result],
+        );
+    }
+
+    /// NEAR sent by the promises of the last call.
+    fn transferred() -> u128 {
+        get_created_receipts()
+            .into_iter()
+            .flat_map(|receipt| receipt.actions)
+            .filter_map(|action| match action {
+                VmAction::Transfer { deposit } => Some(deposit),
+                _ => None,
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_cancel_and_claim_refunds() {
+        let mut contract = setup();
+        let round = contract.sudo_cancel_round(1);
+        assert_eq!(round.status, RoundStatus::Cancelled);
+        assert_eq!(contract.grant_for((1, accounts(1)), None), (U128(0), U128(0)));
+        assert_eq!(contract.get_fee_amount(None), U128(0));
+
+        testing_env!(get_context(accounts(2)).build());
+        contract.claim_refund(1, None);
+        // Platform fees are refunded too.
+        let vote_refund = U128(3 * ONE_NEAR / 10);
+        assert_eq!(transferred(), vote_refund.0);
+        resolve_with(PromiseResult::Successful(vec![]));
+        assert_eq!(contract.on_claim_refund(1, accounts(2), vote_refund, None), vote_refund);
+        assert!(get_logs().last().unwrap().contains("\"event\":\"refund\""));
+
+        testing_env!(get_context(accounts(2)).build());
+        contract.claim_refund(1, Some(token()));
+        resolve_with(PromiseResult::Successful(vec![]));
+        assert_eq!(contract.on_claim_refund(1, accounts(2), U128(10), Some(token())), U128(10));
+
+        testing_env!(get_context(accounts(3)).build());
+        contract.claim_refund(1, None);
+        let donation_refund = U128(ONE_NEAR);
+        assert_eq!(transferred(), donation_refund.0);
+        resolve_with(PromiseResult::Successful(vec![]));
+        assert_eq!(
+            contract.on_claim_refund(1, accounts(3), donation_refund, None),
+            donation_refund
+        );
+    }
+
+    #[test]
+    fn test_claim_refund_again_after_failed_transfer() {
+        let mut contract = setup();
+        contract.sudo_cancel_round(1);
+        testing_env!(get_context(accounts(3)).build());
+        contract.claim_refund(1, None);
+        let amount = U128(ONE_NEAR);
+        resolve_with(PromiseResult::Failed);
+        assert_eq!(contract.on_claim_refund(1, accounts(3), amount, None), U128(0));
+
+        testing_env!(get_context(accounts(3)).build());
+        contract.claim_refund(1, None);
+        resolve_with(PromiseResult::Successful(vec![]));
+        assert_eq!(contract.on_claim_refund(1, accounts(3), amount, None), amount);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_NOTHING_TO_REFUND")]
+    fn test_claim_refund_twice() {
+        let mut contract = setup();
+        contract.sudo_cancel_round(1);
+        testing_env!(get_context(accounts(2)).build());
+        contract.claim_refund(1, None);
+        contract.claim_refund(1, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_ROUND_NOT_ACTIVE")]
+    fn test_reopen_cancelled_round() {
+        let mut contract = setup();
+        contract.sudo_cancel_round(1);
+        contract.sudo_update_round(1, true, None, Some(300));
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_ROUND_NOT_CANCELLED")]
+    fn test_claim_refund_not_cancelled() {
+        let mut contract = setup();
+        testing_env!(get_context(accounts(2)).build());
+        contract.claim_refund(1, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_ROUND_NOT_ACTIVE")]
+    fn test_cancel_after_withdraw() {
+        let mut contract = setup();
+        testing_env!(get_context(accounts(0)).block_timestamp(PAYOUT_AT * 1_000_000_000).build());
+        contract.sudo_finish_round(1, None);
+        testing_env!(get_context(accounts(1)).block_timestamp(PAYOUT_AT * 1_000_000_000).build());
+        contract.withdraw((1, accounts(1)), U128(1), None);
+        testing_env!(get_context(accounts(0)).build());
+        contract.sudo_cancel_round(1);
+    }
+}
@@ -0,0 +1,242 @@
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use near_contract_standards::fungible_token::receiver::FungibleTokenReceiver;
+    use near_contract_standards::storage_management::StorageManagement;
+    use near_sdk::json_types::U128;
+    use near_sdk::mock::VmAction;
+    use near_sdk::serde_json;
+    use near_sdk::test_utils::{accounts, get_created_receipts, get_logs, VMContextBuilder};
+    use near_sdk::{testing_env, AccountId, Gas, PromiseOrValue, PromiseResult, ONE_NEAR};
+    use near_sdk::{RuntimeFeesConfig, VMConfig};
+
+    use grant::*;
+
+    fn get_context(predecessor_account_id: AccountId) -> VMContextBuilder {
+        let mut builder = VMContextBuilder::new();
+        builder
+            .current_account_id(accounts(0))
+            .signer_account_id(predecessor_account_id.clone())
+            .predecessor_account_id(predecessor_account_id);
+        builder
+    }
+
+    fn register_accounts(contract: &mut Contract) {
+        testing_env!(get_context(accounts(0)).attached_deposit(ONE_NEAR).build());
+        for index in 0..6 {
+            contract.storage_deposit(Some(accounts(index)), None);
+        }
+        testing_env!(get_context(accounts(0)).build());
+    }
+
+    fn registry() -> AccountId {
+        "registry.near".parse().unwrap()
+    }
+
+    fn token() -> AccountId {
+        "token.near".parse().unwrap()
+    }
+
+    /// Round 1 with a registry and an approved project of accounts(1).
+    fn setup() -> (Contract, ProjectId) {
+        setup_with_params(RoundParams::default())
+    }
+
+    fn setup_with_params(params: RoundParams) -> (Contract, ProjectId) {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = Contract::init();
+        register_accounts(&mut contract);
+        contract.sudo_new_round(0, 100, None, Some(params));
+        contract.sudo_set_round_token(1, token(), Some(U128(10)));
+        assert_eq!(
+            contract.sudo_set_round_registry(1, Some(registry())).registry_id,
+            Some(registry())
+        );
+        testing_env!(get_context(accounts(1)).build());
+        contract.new_project(Some(1), "a".into(), "b".into(), "c".into(), "d".into());
+        testing_env!(get_context(accounts(0)).build());
+        contract.sudo_approve_project((1, accounts(1)), None);
+        (contract, (1, accounts(1)))
+    }
+
+    fn resolve_with(result: PromiseResult) {
+        testing_env!(
+            get_context(accounts(0)).build(),
+            VMConfig::test(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![result],
+        );
+    }
+
+    fn answer(eligible: bool) -> PromiseResult {
+        PromiseResult::Successful(serde_json::to_vec(&eligible).unwrap())
+    }
+
+    #[test]
+    fn test_vote_applied_for_eligible_voter() {
+        let (mut contract, project_id) = setup();
+        testing_env!(get_context(accounts(2)).attached_deposit(ONE_NEAR).build());
+        assert!(matches!(contract.vote(project_id.clone(), 2), PromiseOrValue::Promise(_)));
+        assert_eq!(contract.get_project(project_id.clone()).unwrap().total_votes, 0);
+
+        resolve_with(answer(true));
+        let project = contract.on_eligible_vote(accounts(2), project_id, 2, U128(ONE_NEAR));
+        assert_eq!(project.unwrap().total_votes, 2);
+        assert!(get_logs().last().unwrap().contains("\"event\":\"vote\""));
+    }
+
+    #[test]
+    fn test_vote_refunded_for_not_eligible_voter() {
+        let (mut contract, project_id) = setup();
+        for result in [answer(false), PromiseResult::Failed] {
+            resolve_with(result);
+            let project =
+                contract.on_eligible_vote(accounts(2), project_id.clone(), 2, U128(ONE_NEAR));
+            assert!(project.is_none());
+            assert!(get_logs().last().unwrap().contains("\"event\":\"vote_refunded\""));
+        }
+        assert_eq!(contract.get_project(project_id).unwrap().total_votes, 0);
+        assert_eq!(contract.get_fee_amount(None), U128(0));
+    }
+
+    #[test]
+    fn test_vote_refunded_when_round_ended() {
+        let (mut contract, project_id) = setup();
+        testing_env!(
+            get_context(accounts(0)).block_timestamp(200 * 1_000_000_000).build(),
+            VMConfig::test(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![answer(true)],
+        );
+        assert!(contract.on_eligible_vote(accounts(2), project_id, 2, U128(ONE_NEAR)).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_NOT_ENOUGH")]
+    fn test_vote_with_registry_requires_deposit() {
+        let (mut contract, project_id) = setup();
+        testing_env!(get_context(accounts(2)).attached_deposit(ONE_NEAR / 10).build());
+        contract.vote(project_id, 2);
+    }
+
+    fn unregistered() -> AccountId {
+        "unregistered.near".parse().unwrap()
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_STORAGE_NOT_REGISTERED")]
+    fn test_vote_with_registry_requires_storage() {
+        let (mut contract, project_id) = setup();
+        testing_env!(get_context(unregistered()).attached_deposit(ONE_NEAR).build());
+        contract.vote(project_id, 2);
+    }
+
+    #[test]
+    fn test_vote_refunded_for_unregistered_voter() {
+        let (mut contract, project_id) = setup();
+        resolve_with(answer(true));
+        assert!(contract
+            .on_eligible_vote(unregistered(), project_id.clone(), 2, U128(ONE_NEAR))
+            .is_none());
+        assert!(get_logs().last().unwrap().contains("\"event\":\"vote_refunded\""));
+        assert_eq!(contract.get_project(project_id).unwrap().total_votes, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_TOO_MANY_VOTES")]
+    fn test_vote_with_registry_over_max_votes() {
+        let (mut contract, project_id) =
+            setup_with_params(RoundParams { max_votes_per_voter: Some(1), ..Default::default() });
+        testing_env!(get_context(accounts(2)).attached_deposit(ONE_NEAR).build());
+        contract.vote(project_id, 2);
+    }
+
+    #[test]
+    fn test_vote_refunded_over_max_votes() {
+        let (mut contract, project_id) =
+            setup_with_params(RoundParams { max_votes_per_voter: Some(3), ..Default::default() });
+        resolve_with(answer(true));
+        assert!(contract
+            .on_eligible_vote(accounts(2), project_id.clone(), 2, U128(ONE_NEAR))
+            .is_some());
+        assert!(contract
+            .on_eligible_vote(accounts(2), project_id.clone(), 2, U128(ONE_NEAR))
+            .is_none());
+        assert!(get_logs().last().unwrap().contains("\"event\":\"vote_refunded\""));
+        assert_eq!(contract.get_project(project_id).unwrap().total_votes, 2);
+    }
+
+    #[test]
+    fn test_vote_refunded_without_gas_to_apply() {
+        let (mut contract, project_id) = setup();
+        testing_env!(
+            get_context(accounts(0)).prepaid_gas(GAS_FOR_APPLY_VOTE - Gas(1)).build(),
+            VMConfig::test(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![answer(true)],
+        );
+        assert!(contract.on_eligible_vote(accounts(2), project_id, 2, U128(ONE_NEAR)).is_none());
+    }
+
+    #[test]
+    fn test_token_vote_checked_by_registry() {
+        let (mut contract, project_id) = setup();
+        testing_env!(get_context(token()).build());
+        let msg =
+            serde_json::to_string(&FtMessage::Vote { project_id: project_id.clone(), votes: 2 })
+                .unwrap();
+        assert!(matches!(
+            contract.ft_on_transfer(accounts(2), U128(100), msg),
+            PromiseOrValue::Promise(_)
+        ));
+
+        resolve_with(answer(false));
+        let unused =
+            contract.on_eligible_token_vote(token(), accounts(2), U128(100), project_id.clone(), 2);
+        assert_eq!(unused, U128(100));
+
+        resolve_with(answer(true));
+        let unused =
+            contract.on_eligible_token_vote(token(), accounts(2), U128(100), project_id.clone(), 2);
+        assert_eq!(unused, U128(70));
+        assert_eq!(contract.get_project(project_id).unwrap().total_votes, 2);
+    }
+    #[test]
+    fn test_token_vote_callback_gas_covers_pairs() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = Contract::init();
+        register_accounts(&mut contract);
+        contract.sudo_new_round(0, 100, Some(MatchingStrategy::PairwiseBounded { m: 1 }), None);
+        contract.sudo_set_round_token(1, token(), Some(U128(10)));
+        contract.sudo_set_round_registry(1, Some(registry()));
+        testing_env!(get_context(accounts(1)).build());
+        contract.new_project(Some(1), "a".into(), "b".into(), "c".into(), "d".into());
+        testing_env!(get_context(accounts(0)).build());
+        contract.sudo_approve_project((1, accounts(1)), None);
+        for voter in [accounts(2), accounts(3)] {
+            resolve_with(answer(true));
+            contract.on_eligible_token_vote(token(), voter, U128(100), (1, accounts(1)), 1);
+        }
+
+        testing_env!(get_context(token()).build());
+        let msg =
+            serde_json::to_string(&FtMessage::Vote { project_id: (1, accounts(1)), votes: 1 })
+                .unwrap();
+        drop(contract.ft_on_transfer(accounts(4), U128(100), msg));
+        let gas = get_created_receipts()
+            .into_iter()
+            .flat_map(|receipt| receipt.actions)
+            .find_map(|action| match action {
+                VmAction::FunctionCall { function_name, gas, .. }
+                    if function_name == "on_eligible_token_vote" =>
+                {
+                    Some(gas)
+                }
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(gas, GAS_FOR_RESOLVE_VOTE + Gas(2 * GAS_FOR_PAIR_WEIGHT.0));
+    }
+}
@@ -0,0 +1,119 @@
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use near_contract_standards::storage_management::StorageManagement;
+    use near_sdk::json_types::U128;
+    use near_sdk::test_utils::{accounts, VMContextBuilder};
+    use near_sdk::{testing_env, AccountId, ONE_NEAR};
+
+    use grant::*;
+
+    /// First second of payouts of rounds ending at 100 after the default review.
+    const PAYOUT_AT: u64 = 101 + DEFAULT_REVIEW_DURATION as u64;
+
+    fn get_context(predecessor_account_id: AccountId) -> VMContextBuilder {
+        let mut builder = VMContextBuilder::new();
+        builder
+            .current_account_id(accounts(0))
+            .signer_account_id(predecessor_account_id.clone())
+            .predecessor_account_id(predecessor_account_id);
+        builder
+    }
+
+    fn register_accounts(contract: &mut Contract) {
+        testing_env!(get_context(accounts(0)).attached_deposit(ONE_NEAR).build());
+        for index in 0..6 {
+            contract.storage_deposit(Some(accounts(index)), None);
+        }
+        testing_env!(get_context(accounts(0)).build());
+    }
+
+    /// accounts(3) and accounts(4) co-vote for the project of accounts(1), only accounts(3)
+    /// votes for the project of accounts(2), accounts(5) donates 1 NEAR. Round ends at 100.
+    fn setup() -> Contract {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = Contract::init();
+        register_accounts(&mut contract);
+        contract.sudo_new_round(0, 100, None, None);
+        for owner in [accounts(1), accounts(2)] {
+            testing_env!(get_context(owner.clone()).build());
+            contract.new_project(Some(1), "a".into(), "b".into(), "c".into(), "d".into());
+            testing_env!(get_context(accounts(0)).build());
+            contract.sudo_approve_project((1, owner), None);
+        }
+        testing_env!(get_context(accounts(3)).attached_deposit(ONE_NEAR).build());
+        contract.vote((1, accounts(1)), 1);
+        contract.vote((1, accounts(2)), 2);
+        testing_env!(get_context(accounts(4)).attached_deposit(ONE_NEAR).build());
+        contract.vote((1, accounts(1)), 1);
+        testing_env!(get_context(accounts(5)).attached_deposit(ONE_NEAR).build());
+        contract.donate(Some(1));
+        contract
+    }
+
+    fn finish(contract: &mut Contract) {
+        testing_env!(get_context(accounts(0)).block_timestamp(PAYOUT_AT * 1_000_000_000).build());
+        contract.sudo_finish_round(1, None);
+    }
+
+    #[test]
+    fn test_round_results() {
+        let mut contract = setup();
+        assert!(contract.get_round_results(1, None, None).is_empty());
+        finish(&mut contract);
+
+        let results = contract.get_round_results(1, None, None);
+        assert_eq!(results.len(), 2);
+        let pool = contract.get_round(1).unwrap().support_pool;
+        assert_eq!((results[0].project_id.clone(), results[0].rank), ((1, accounts(1)), 1));
+        assert_eq!((results[0].matched, results[0].share_point), (pool, 10000));
+        assert_eq!((results[1].project_id.clone(), results[1].rank), ((1, accounts(2)), 2));
+        assert_eq!((results[1].matched, results[1].share_point), (U128(0), 0));
+        assert_eq!(results[1].grants, contract.get_project((1, accounts(2))).unwrap().grants);
+
+        let page = contract.get_round_results(1, Some(1), Some(1));
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].rank, 2);
+        let granted = results[0].grants.0 + results[0].matched.0;
+        assert_eq!(contract.grant_for((1, accounts(1)), None), (U128(granted), U128(granted)));
+        assert_eq!(contract.get_round_result((1, accounts(1))).unwrap().rank, 1);
+    }
+
+    #[test]
+    fn test_finish_round_in_batches() {
+        let mut contract = setup();
+        testing_env!(get_context(accounts(0)).block_timestamp(PAYOUT_AT * 1_000_000_000).build());
+        let round = contract.sudo_finish_round(1, Some(1));
+        assert_eq!((round.status, round.ranked_projects), (RoundStatus::Finishing, 1));
+        assert_eq!(contract.get_round_results(1, None, None).len(), 1);
+        let round = contract.sudo_finish_round(1, Some(1));
+        assert_eq!((round.status, round.ranked_projects), (RoundStatus::Finished, 2));
+        let results = contract.get_round_results(1, None, None);
+        assert_eq!(results[1].project_id, (1, accounts(2)));
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_ROUND_NOT_FINISHED")]
+    fn test_withdraw_while_finishing() {
+        let mut contract = setup();
+        testing_env!(get_context(accounts(0)).block_timestamp(PAYOUT_AT * 1_000_000_000).build());
+        contract.sudo_finish_round(1, Some(1));
+        testing_env!(get_context(accounts(1)).block_timestamp(PAYOUT_AT * 1_000_000_000).build());
+        contract.withdraw((1, accounts(1)), U128(1), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_ROUND_NOT_ACTIVE")]
+    fn test_reopen_finished_round() {
+        let mut contract = setup();
+        finish(&mut contract);
+        contract.sudo_update_round(1, true, None, Some(300));
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_ROUND_NOT_FINISHED")]
+    fn test_withdraw_before_finish() {
+        let mut contract = setup();
+        testing_env!(get_context(accounts(1)).block_timestamp(PAYOUT_AT * 1_000_000_000).build());
+        contract.withdraw((1, accounts(1)), U128(1), None);
+    }
+}
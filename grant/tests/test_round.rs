@@ -1,12 +1,14 @@
 #[cfg(all(test, not(target_arch = "wasm32")))]
 mod tests {
-    use near_sdk::test_utils::test_env::alice;
+    use near_contract_standards::storage_management::StorageManagement;
+    use near_sdk::json_types::U128;
     use near_sdk::test_utils::{accounts, VMContextBuilder};
-    use near_sdk::{testing_env, AccountId};
+    use near_sdk::{testing_env, AccountId, ONE_NEAR};
 
     use grant::*;
 
-    const MINT_STORAGE_COST: u128 = 5870000000000000000000;
+    /// First second of payouts of rounds ending at 100 after the default review.
+    const PAYOUT_AT: u64 = 101 + DEFAULT_REVIEW_DURATION as u64;
 
     fn get_context(predecessor_account_id: AccountId) -> VMContextBuilder {
         let mut builder = VMContextBuilder::new();
@@ -17,6 +19,14 @@ mod tests {
         builder
     }
 
+    fn register_accounts(contract: &mut Contract) {
+        testing_env!(get_context(accounts(0)).attached_deposit(ONE_NEAR).build());
+        for index in 0..6 {
+            contract.storage_deposit(Some(accounts(index)), None);
+        }
+        testing_env!(get_context(accounts(0)).build());
+    }
+
     #[test]
     fn test_create_new_round() {
         let context = get_context(accounts(0));
@@ -25,4 +35,418 @@ mod tests {
         let round = contract.sudo_new_default_round();
         assert_eq!(round.id, 1);
     }
+
+    #[test]
+    fn test_concurrent_rounds() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = Contract::init();
+        register_accounts(&mut contract);
+        contract.sudo_new_round(0, 100, None, None);
+        contract.sudo_new_round(0, 200, None, None);
+        assert_eq!(contract.list_active_rounds().len(), 2);
+
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        contract.new_project(Some(1), "a".into(), "b".into(), "c".into(), "d".into());
+        contract.new_project(Some(2), "a".into(), "b".into(), "c".into(), "d".into());
+        testing_env!(context.predecessor_account_id(accounts(2)).build());
+        contract.new_project(Some(2), "a".into(), "b".into(), "c".into(), "d".into());
+        testing_env!(context.predecessor_account_id(accounts(0)).build());
+        contract.sudo_approve_project((2, accounts(1)), None);
+
+        testing_env!(context
+            .predecessor_account_id(accounts(2))
+            .attached_deposit(ONE_NEAR)
+            .build());
+        contract.donate(Some(2));
+        contract.vote((2, accounts(1)), 1);
+
+        assert_eq!(contract.list_projects(1, None, None, None).len(), 1);
+        assert_eq!(contract.list_projects(2, None, None, None).len(), 2);
+        assert_eq!(contract.get_round(1).unwrap().support_pool.0, 0);
+        assert_eq!(contract.get_round(2).unwrap().support_pool.0, ONE_NEAR * 95 / 100);
+        assert_eq!(contract.get_project((1, accounts(1))).unwrap().total_votes, 0);
+        assert_eq!(contract.get_project((2, accounts(1))).unwrap().total_votes, 1);
+
+        testing_env!(context.block_timestamp(150 * 1_000_000_000).build());
+        assert_eq!(contract.list_active_rounds().len(), 1);
+    }
+
+    /// Round 1 ends at 100 with projects of accounts(1..=3) voted by `voters`
+    /// and a donation of 1 NEAR, round 2 is still active.
+    fn setup_finished_round(voters: Vec<AccountId>) -> Contract {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = Contract::init();
+        register_accounts(&mut contract);
+        contract.sudo_new_round(0, 100, None, None);
+        contract.sudo_new_round(0, 1000, None, None);
+        for owner in [accounts(1), accounts(2), accounts(3)] {
+            testing_env!(context.predecessor_account_id(owner.clone()).build());
+            contract.new_project(Some(1), "a".into(), "b".into(), "c".into(), "d".into());
+            testing_env!(context.predecessor_account_id(accounts(0)).build());
+            contract.sudo_approve_project((1, owner), None);
+        }
+        testing_env!(context.attached_deposit(ONE_NEAR).build());
+        contract.donate(Some(1));
+        for voter in voters {
+            testing_env!(context.predecessor_account_id(voter).build());
+            for owner in [accounts(1), accounts(2), accounts(3)] {
+                contract.vote((1, owner), 1);
+            }
+        }
+        testing_env!(context
+            .predecessor_account_id(accounts(0))
+            .attached_deposit(0)
+            .block_timestamp(PAYOUT_AT * 1_000_000_000)
+            .build());
+        contract.sudo_finish_round(1, None);
+        contract
+    }
+
+    #[test]
+    fn test_rollover_unmatched_pool_to_next_round() {
+        let mut contract = setup_finished_round(vec![accounts(4)]);
+        let pool = contract.get_round(1).unwrap().support_pool.0;
+        assert_eq!(
+            contract.grant_for((1, accounts(1)), None).0,
+            U128(contract.get_project((1, accounts(1))).unwrap().grants.0)
+        );
+
+        let round = contract.sudo_rollover_round(1, RolloverTarget::Round { round_id: 2 });
+        let rollover = round.rollover.unwrap();
+        assert_eq!(rollover.target, RolloverTarget::Round { round_id: 2 });
+        assert_eq!(rollover.amount.0, pool);
+        let next = contract.get_round(2).unwrap();
+        assert_eq!(next.support_pool.0, pool);
+        assert_eq!(next.pure_support_pool.0, 0);
+        assert_eq!(next.rolled_over_from, vec![1]);
+    }
+
+    #[test]
+    fn test_rollover_dust_to_reserve() {
+        let mut contract = setup_finished_round(vec![accounts(4), accounts(5)]);
+        let pool = contract.get_round(1).unwrap().support_pool.0;
+        let matched = contract.grant_for((1, accounts(1)), None).1 .0
+            - contract.get_project((1, accounts(1))).unwrap().grants.0;
+        assert_eq!(matched, pool / 3);
+
+        contract.sudo_rollover_round(1, RolloverTarget::Reserve);
+        let reserve = contract.get_reserve(None).0;
+        assert_eq!(reserve, pool - pool / 3 * 3);
+        assert!(reserve > 0);
+
+        let round = contract.sudo_fund_round(2, U128(reserve), None);
+        assert_eq!(round.support_pool.0, reserve);
+        assert_eq!(contract.get_reserve(None).0, 0);
+    }
+
+    #[test]
+    fn test_cancel_returns_rollover_to_reserve() {
+        let mut contract = setup_finished_round(vec![accounts(4)]);
+        let pool = contract.get_round(1).unwrap().support_pool.0;
+        contract.sudo_rollover_round(1, RolloverTarget::Round { round_id: 2 });
+        let round = contract.sudo_cancel_round(2);
+        assert_eq!(round.support_pool.0, 0);
+        assert_eq!(round.funded.0, 0);
+        assert_eq!(contract.get_reserve(None).0, pool);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_ROLLED_OVER")]
+    fn test_rollover_twice() {
+        let mut contract = setup_finished_round(vec![accounts(4)]);
+        contract.sudo_rollover_round(1, RolloverTarget::Reserve);
+        contract.sudo_rollover_round(1, RolloverTarget::Round { round_id: 2 });
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_ROUND_NOT_FINISHED")]
+    fn test_rollover_active_round() {
+        let mut contract = setup_finished_round(vec![accounts(4)]);
+        contract.sudo_rollover_round(2, RolloverTarget::Reserve);
+    }
+
+    /// Round 1 with `params` and approved projects of accounts(1) and accounts(2).
+    fn setup_with_params(params: RoundParams) -> Contract {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = Contract::init();
+        register_accounts(&mut contract);
+        contract.sudo_new_round(0, 100, None, Some(params));
+        for owner in [accounts(1), accounts(2)] {
+            testing_env!(context.predecessor_account_id(owner.clone()).build());
+            contract.new_project(Some(1), "a".into(), "b".into(), "c".into(), "d".into());
+            testing_env!(context.predecessor_account_id(accounts(0)).build());
+            contract.sudo_approve_project((1, owner), None);
+        }
+        contract
+    }
+
+    #[test]
+    fn test_round_params() {
+        let mut contract = setup_with_params(RoundParams {
+            vote_cost: Some(U128(ONE_NEAR / 100)),
+            fee_point: Some(1000),
+            ..Default::default()
+        });
+        contract.sudo_config(Some(0), None, None, None);
+        let round = contract.get_round(1).unwrap();
+        assert_eq!(round.vote_cost, U128(ONE_NEAR / 100));
+        assert_eq!(round.params.fee_point, Some(1000));
+
+        testing_env!(get_context(accounts(3)).attached_deposit(ONE_NEAR).build());
+        contract.vote((1, accounts(1)), 2);
+        contract.donate(Some(1));
+        assert_eq!(contract.get_round(1).unwrap().fees, U128(3 * ONE_NEAR / 1000 + ONE_NEAR / 10));
+
+        testing_env!(get_context(accounts(0)).build());
+        let round = contract.sudo_new_default_round();
+        assert_eq!(round.params.vote_cost, Some(U128(ONE_NEAR / 10)));
+        assert_eq!(round.params.fee_point, Some(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_TOO_MANY_VOTES")]
+    fn test_max_votes_per_voter() {
+        let mut contract =
+            setup_with_params(RoundParams { max_votes_per_voter: Some(3), ..Default::default() });
+        testing_env!(get_context(accounts(3)).attached_deposit(ONE_NEAR).build());
+        contract.vote((1, accounts(1)), 2);
+        contract.vote((1, accounts(2)), 1);
+        contract.vote((1, accounts(2)), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_TOO_MANY_PROJECTS")]
+    fn test_max_projects() {
+        setup_with_params(RoundParams { max_projects: Some(1), ..Default::default() });
+    }
+
+    #[test]
+    fn test_matching_cap() {
+        let mut contract = setup_with_params(RoundParams {
+            matching_cap: Some(U128(ONE_NEAR / 10)),
+            ..Default::default()
+        });
+        testing_env!(get_context(accounts(3)).attached_deposit(ONE_NEAR).build());
+        contract.donate(Some(1));
+        contract.vote((1, accounts(1)), 1);
+        testing_env!(get_context(accounts(4)).attached_deposit(ONE_NEAR).build());
+        contract.vote((1, accounts(1)), 1);
+
+        testing_env!(get_context(accounts(0)).block_timestamp(PAYOUT_AT * 1_000_000_000).build());
+        let project = contract.get_project((1, accounts(1))).unwrap();
+        assert_eq!(
+            contract.grant_for((1, accounts(1)), None).1 .0,
+            project.grants.0 + ONE_NEAR / 10
+        );
+        contract.sudo_finish_round(1, None);
+        let rollover = contract.sudo_rollover_round(1, RolloverTarget::Reserve).rollover.unwrap();
+        assert_eq!(rollover.amount.0, ONE_NEAR * 95 / 100 - ONE_NEAR / 10);
+    }
+
+    #[test]
+    fn test_scheduled_rounds() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = Contract::init();
+        register_accounts(&mut contract);
+        contract.sudo_schedule_round(200, 299, None, None);
+        contract.sudo_schedule_round(100, 199, None, None);
+        assert!(contract.get_current_round().is_none());
+        let scheduled = contract.list_scheduled_rounds(None, None);
+        assert_eq!(scheduled.iter().map(|round| round.id).collect::<Vec<_>>(), vec![2, 1]);
+
+        testing_env!(context
+            .predecessor_account_id(accounts(1))
+            .block_timestamp(150 * 1_000_000_000)
+            .attached_deposit(ONE_NEAR)
+            .build());
+        assert_eq!(contract.get_current_round().unwrap().id, 2);
+        assert_eq!(contract.donate(None).id, 2);
+        assert_eq!(
+            contract.new_project(None, "a".into(), "b".into(), "c".into(), "d".into()).round_id,
+            2
+        );
+
+        testing_env!(context.block_timestamp(250 * 1_000_000_000).build());
+        assert_eq!(contract.get_current_round().unwrap().id, 1);
+        assert_eq!(
+            contract.new_project(None, "a".into(), "b".into(), "c".into(), "d".into()).round_id,
+            1
+        );
+
+        testing_env!(context.block_timestamp(300 * 1_000_000_000).build());
+        assert!(contract.get_current_round().is_none());
+    }
+
+    #[test]
+    fn test_unscheduled_round() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = Contract::init();
+        register_accounts(&mut contract);
+        contract.sudo_new_round(0, 100, None, None);
+        assert_eq!(contract.list_active_rounds().len(), 1);
+        assert!(contract.list_scheduled_rounds(None, None).is_empty());
+        assert!(contract.get_current_round().is_none());
+
+        testing_env!(context
+            .predecessor_account_id(accounts(1))
+            .attached_deposit(ONE_NEAR)
+            .build());
+        assert_eq!(contract.donate(Some(1)).pure_support_pool.0, ONE_NEAR);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_ROUND_OVERLAP")]
+    fn test_schedule_overlapping_round() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = Contract::init();
+        contract.sudo_schedule_round(100, 199, None, None);
+        contract.sudo_schedule_round(150, 250, None, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_NO_CURRENT_ROUND")]
+    fn test_donate_without_current_round() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = Contract::init();
+        contract.sudo_schedule_round(100, 199, None, None);
+        testing_env!(get_context(accounts(1)).attached_deposit(ONE_NEAR).build());
+        contract.donate(None);
+    }
+
+    #[test]
+    fn test_default_rounds_queue() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = Contract::init();
+        contract.sudo_config(None, Some(100), None, None);
+        let first = contract.sudo_new_default_round();
+        let second = contract.sudo_new_default_round();
+        assert_eq!((first.start_at, first.end_at), (0, 100));
+        assert_eq!((second.start_at, second.end_at), (101, 201));
+
+        // Cancelled and rescheduled rounds free their slots on the calendar.
+        contract.sudo_cancel_round(second.id);
+        contract.sudo_update_round(first.id, true, None, Some(150));
+        assert_eq!(contract.list_scheduled_rounds(None, None).len(), 1);
+        assert_eq!(contract.sudo_schedule_round(151, 200, None, None).id, 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_WRONG_END_AT")]
+    fn test_update_round_empty_timeline() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = Contract::init();
+        contract.sudo_new_round(100, 200, None, None);
+        contract.sudo_update_round(1, true, None, Some(100));
+    }
+
+    /// Round 1 with applications from 10 to 50, voting from 100 to 199 and payouts from 250.
+    fn setup_phased_round() -> Contract {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = Contract::init();
+        register_accounts(&mut contract);
+        let phases =
+            RoundPhases { application_start_at: 10, application_end_at: 50, payout_at: 250 };
+        contract.sudo_new_round(
+            100,
+            199,
+            None,
+            Some(RoundParams { phases: Some(phases), ..Default::default() }),
+        );
+        contract
+    }
+
+    #[test]
+    fn test_round_phases() {
+        let mut contract = setup_phased_round();
+        let mut context = get_context(accounts(1));
+        let phase_at = |context: &mut VMContextBuilder, contract: &Contract, at: u64| {
+            testing_env!(context.block_timestamp(at * 1_000_000_000).build());
+            contract.current_phase(1)
+        };
+        assert_eq!(phase_at(&mut context, &contract, 0), RoundPhase::Upcoming);
+        assert_eq!(phase_at(&mut context, &contract, 20), RoundPhase::Application);
+        contract.new_project(Some(1), "a".into(), "b".into(), "c".into(), "d".into());
+        assert_eq!(phase_at(&mut context, &contract, 70), RoundPhase::Upcoming);
+        assert_eq!(phase_at(&mut context, &contract, 150), RoundPhase::Voting);
+        assert_eq!(phase_at(&mut context, &contract, 220), RoundPhase::Review);
+        assert_eq!(phase_at(&mut context, &contract, 260), RoundPhase::Payout);
+
+        testing_env!(context.predecessor_account_id(accounts(0)).build());
+        contract.sudo_finish_round(1, None);
+        assert_eq!(contract.current_phase(1), RoundPhase::Payout);
+    }
+
+    #[test]
+    fn test_apply_to_scheduled_round() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = Contract::init();
+        register_accounts(&mut contract);
+        let phases =
+            RoundPhases { application_start_at: 10, application_end_at: 50, payout_at: 250 };
+        let params = RoundParams { phases: Some(phases), ..Default::default() };
+        contract.sudo_schedule_round(100, 199, None, Some(params));
+        testing_env!(get_context(accounts(1)).block_timestamp(20 * 1_000_000_000).build());
+        assert!(contract.get_current_round().is_none());
+        let project = contract.new_project(None, "a".into(), "b".into(), "c".into(), "d".into());
+        assert_eq!(project.round_id, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_NO_CURRENT_ROUND")]
+    fn test_apply_without_application_round() {
+        let mut contract = setup_phased_round();
+        testing_env!(get_context(accounts(1)).block_timestamp(20 * 1_000_000_000).build());
+        contract.new_project(None, "a".into(), "b".into(), "c".into(), "d".into());
+    }
+
+    #[test]
+    fn test_default_review_window() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = Contract::init();
+        contract.sudo_config(None, None, None, Some(50));
+        let round = contract.sudo_new_round(0, 100, None, None);
+        assert_eq!(round.params.review_duration, Some(50));
+        assert_eq!(round.phases().payout_at, 151);
+        testing_env!(context.block_timestamp(150 * 1_000_000_000).build());
+        assert_eq!(contract.current_phase(1), RoundPhase::Review);
+        testing_env!(context.block_timestamp(151 * 1_000_000_000).build());
+        assert_eq!(contract.current_phase(1), RoundPhase::Payout);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_NOT_APPLICATION_PHASE")]
+    fn test_apply_during_voting() {
+        let mut contract = setup_phased_round();
+        testing_env!(get_context(accounts(1)).block_timestamp(150 * 1_000_000_000).build());
+        contract.new_project(Some(1), "a".into(), "b".into(), "c".into(), "d".into());
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_ROUND_IN_REVIEW")]
+    fn test_finish_during_review() {
+        let mut contract = setup_phased_round();
+        testing_env!(get_context(accounts(0)).block_timestamp(220 * 1_000_000_000).build());
+        contract.sudo_finish_round(1, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_WRONG_PHASES")]
+    fn test_applications_overlap_voting() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = Contract::init();
+        let phases =
+            RoundPhases { application_start_at: 10, application_end_at: 100, payout_at: 250 };
+        contract.sudo_new_round(
+            100,
+            199,
+            None,
+            Some(RoundParams { phases: Some(phases), ..Default::default() }),
+        );
+    }
 }
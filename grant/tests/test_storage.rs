@@ -0,0 +1,96 @@
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use near_contract_standards::storage_management::StorageManagement;
+    use near_sdk::json_types::U128;
+    use near_sdk::test_utils::{accounts, VMContextBuilder};
+    use near_sdk::{testing_env, AccountId, ONE_NEAR};
+
+    use grant::*;
+
+    fn get_context(predecessor_account_id: AccountId) -> VMContextBuilder {
+        let mut builder = VMContextBuilder::new();
+        builder
+            .current_account_id(accounts(0))
+            .signer_account_id(predecessor_account_id.clone())
+            .predecessor_account_id(predecessor_account_id);
+        builder
+    }
+
+    fn setup() -> Contract {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = Contract::init();
+        contract.sudo_new_round(0, 100, None, None);
+        contract
+    }
+
+    fn new_project(contract: &mut Contract) {
+        testing_env!(get_context(accounts(1)).build());
+        contract.new_project(Some(1), "a".into(), "b".into(), "c".into(), "d".into());
+        testing_env!(get_context(accounts(0)).build());
+        contract.sudo_approve_project((1, accounts(1)), None);
+    }
+
+    #[test]
+    fn test_storage_deposit_and_charge() {
+        let mut contract = setup();
+        let min = contract.storage_balance_bounds().min;
+        testing_env!(get_context(accounts(1)).attached_deposit(ONE_NEAR).build());
+        let balance = contract.storage_deposit(None, None);
+        assert_eq!(balance.total, U128(ONE_NEAR));
+        assert!(balance.available.0 > ONE_NEAR - min.0);
+
+        new_project(&mut contract);
+        let after_project = contract.storage_balance_of(accounts(1)).unwrap();
+        assert_eq!(after_project.total, U128(ONE_NEAR));
+        assert!(after_project.available.0 < balance.available.0);
+
+        testing_env!(get_context(accounts(1)).attached_deposit(1).build());
+        let withdrawn = contract.storage_withdraw(None);
+        assert_eq!(withdrawn.total.0, ONE_NEAR - after_project.available.0);
+        assert_eq!(withdrawn.available, U128(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_STORAGE_NOT_REGISTERED")]
+    fn test_new_project_requires_registration() {
+        let mut contract = setup();
+        new_project(&mut contract);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_NOT_ENOUGH_STORAGE_DEPOSIT")]
+    fn test_vote_requires_storage_balance() {
+        let mut contract = setup();
+        let min = contract.storage_balance_bounds().min;
+        testing_env!(get_context(accounts(0)).attached_deposit(ONE_NEAR).build());
+        contract.storage_deposit(Some(accounts(1)), None);
+        testing_env!(get_context(accounts(0)).attached_deposit(min.0).build());
+        contract.storage_deposit(Some(accounts(2)), Some(true));
+        new_project(&mut contract);
+
+        testing_env!(get_context(accounts(2)).attached_deposit(ONE_NEAR).build());
+        contract.vote((1, accounts(1)), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_STORAGE_IN_USE")]
+    fn test_unregister_in_use() {
+        let mut contract = setup();
+        testing_env!(get_context(accounts(1)).attached_deposit(ONE_NEAR).build());
+        contract.storage_deposit(None, None);
+        new_project(&mut contract);
+        testing_env!(get_context(accounts(1)).attached_deposit(1).build());
+        contract.storage_unregister(None);
+    }
+
+    #[test]
+    fn test_unregister() {
+        let mut contract = setup();
+        testing_env!(get_context(accounts(1)).attached_deposit(ONE_NEAR).build());
+        contract.storage_deposit(None, None);
+        testing_env!(get_context(accounts(1)).attached_deposit(1).build());
+        assert!(contract.storage_unregister(None));
+        assert!(contract.storage_balance_of(accounts(1)).is_none());
+        assert!(!contract.storage_unregister(None));
+    }
+}
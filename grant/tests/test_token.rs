@@ -0,0 +1,103 @@
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use near_contract_standards::fungible_token::receiver::FungibleTokenReceiver;
+    use near_contract_standards::storage_management::StorageManagement;
+    use near_sdk::json_types::U128;
+    use near_sdk::test_utils::{accounts, VMContextBuilder};
+    use near_sdk::{testing_env, AccountId, PromiseOrValue, ONE_NEAR};
+
+    use grant::*;
+
+    fn get_context(predecessor_account_id: AccountId) -> VMContextBuilder {
+        let mut builder = VMContextBuilder::new();
+        builder
+            .current_account_id(accounts(0))
+            .signer_account_id(predecessor_account_id.clone())
+            .predecessor_account_id(predecessor_account_id);
+        builder
+    }
+
+    fn register_accounts(contract: &mut Contract) {
+        testing_env!(get_context(accounts(0)).attached_deposit(ONE_NEAR).build());
+        for index in 0..6 {
+            contract.storage_deposit(Some(accounts(index)), None);
+        }
+        testing_env!(get_context(accounts(0)).build());
+    }
+
+    fn token() -> AccountId {
+        "dummy_ft.testnet".parse().unwrap()
+    }
+
+    fn unused(value: PromiseOrValue<U128>) -> U128 {
+        match value {
+            PromiseOrValue::Value(value) => value,
+            PromiseOrValue::Promise(_) => panic!("expected value"),
+        }
+    }
+
+    fn setup() -> (Contract, ProjectId) {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = Contract::init();
+        register_accounts(&mut contract);
+        contract.sudo_new_round(0, 100, None, None);
+        contract.sudo_set_round_token(1, token(), Some(U128(10)));
+
+        testing_env!(get_context(accounts(1)).build());
+        contract.new_project(Some(1), "a".into(), "b".into(), "c".into(), "d".into());
+        testing_env!(get_context(accounts(0)).build());
+        contract.sudo_approve_project((1, accounts(1)), None);
+        (contract, (1, accounts(1)))
+    }
+
+    #[test]
+    fn test_ft_vote_and_donate() {
+        let (mut contract, project_id) = setup();
+        testing_env!(get_context(token()).build());
+        let msg =
+            serde_json::to_string(&FtMessage::Vote { project_id: project_id.clone(), votes: 2 })
+                .unwrap();
+        // 2 votes weigh 1 + 2 = 3, so 30 of 100 is used.
+        assert_eq!(unused(contract.ft_on_transfer(accounts(2), U128(100), msg)), U128(70));
+        let project = contract.get_project(project_id.clone()).unwrap();
+        assert_eq!(project.total_votes, 2);
+        assert_eq!(project.grants, U128(0));
+        assert_eq!(project.token_grants.get(&token()), Some(&U128(29)));
+        assert_eq!(contract.get_round(1).unwrap().token_fees.get(&token()), Some(&U128(1)));
+
+        let msg = serde_json::to_string(&FtMessage::Donate { round_id: 1 }).unwrap();
+        assert_eq!(unused(contract.ft_on_transfer(accounts(2), U128(1000), msg)), U128(0));
+        let round = contract.get_round(1).unwrap();
+        assert_eq!(round.token_pools.get(&token()).unwrap().support_pool, U128(950));
+        assert_eq!(round.token_fees.get(&token()), Some(&U128(51)));
+
+        testing_env!(get_context(accounts(1)).block_timestamp(200 * 1_000_000_000).build());
+        assert_eq!(contract.grant_for(project_id.clone(), Some(token())).0, U128(29));
+        assert_eq!(contract.grant_for(project_id, None).0, U128(0));
+
+        // Fees of the round are collected once it finishes.
+        assert_eq!(contract.get_fee_amount(Some(token())), U128(0));
+        let payout_at = 101 + DEFAULT_REVIEW_DURATION as u64;
+        testing_env!(get_context(accounts(0)).block_timestamp(payout_at * 1_000_000_000).build());
+        contract.sudo_finish_round(1, None);
+        assert_eq!(contract.get_fee_amount(Some(token())), U128(51));
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_TOKEN_NOT_ACCEPTED")]
+    fn test_ft_vote_not_accepted_token() {
+        let (mut contract, project_id) = setup();
+        testing_env!(get_context(accounts(3)).build());
+        let msg = serde_json::to_string(&FtMessage::Vote { project_id, votes: 1 }).unwrap();
+        contract.ft_on_transfer(accounts(2), U128(100), msg);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_NOT_ENOUGH")]
+    fn test_ft_vote_not_enough() {
+        let (mut contract, project_id) = setup();
+        testing_env!(get_context(token()).build());
+        let msg = serde_json::to_string(&FtMessage::Vote { project_id, votes: 2 }).unwrap();
+        contract.ft_on_transfer(accounts(2), U128(29), msg);
+    }
+}
@@ -0,0 +1,172 @@
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use std::collections::HashMap;
+
+    use near_contract_standards::fungible_token::receiver::FungibleTokenReceiver;
+    use near_contract_standards::storage_management::StorageManagement;
+    use near_sdk::json_types::U128;
+    use near_sdk::serde_json;
+    use near_sdk::test_utils::{accounts, VMContextBuilder};
+    use near_sdk::{env, testing_env, AccountId, ONE_NEAR};
+
+    use grant::*;
+
+    fn get_context(predecessor_account_id: AccountId) -> VMContextBuilder {
+        let mut builder = VMContextBuilder::new();
+        builder
+            .current_account_id(accounts(0))
+            .signer_account_id(predecessor_account_id.clone())
+            .predecessor_account_id(predecessor_account_id);
+        builder
+    }
+
+    fn register_accounts(contract: &mut Contract) {
+        testing_env!(get_context(accounts(0)).attached_deposit(ONE_NEAR).build());
+        for index in 0..6 {
+            contract.storage_deposit(Some(accounts(index)), None);
+        }
+        testing_env!(get_context(accounts(0)).build());
+    }
+
+    fn token() -> AccountId {
+        "token.near".parse().unwrap()
+    }
+
+    /// accounts(3) votes 2 for the project of accounts(1), 1 in tokens and then 1 in NEAR
+    /// for the project of accounts(2).
+    fn setup() -> Contract {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = Contract::init();
+        register_accounts(&mut contract);
+        contract.sudo_new_round(0, 100, None, None);
+        contract.sudo_set_round_token(1, token(), Some(U128(10)));
+        for owner in [accounts(1), accounts(2)] {
+            testing_env!(get_context(owner.clone()).build());
+            contract.new_project(Some(1), "a".into(), "b".into(), "c".into(), "d".into());
+            testing_env!(get_context(accounts(0)).build());
+            contract.sudo_approve_project((1, owner), None);
+        }
+
+        testing_env!(get_context(accounts(3)).attached_deposit(ONE_NEAR).build());
+        contract.vote((1, accounts(1)), 2);
+        testing_env!(get_context(token()).build());
+        let msg =
+            serde_json::to_string(&FtMessage::Vote { project_id: (1, accounts(2)), votes: 1 })
+                .unwrap();
+        contract.ft_on_transfer(accounts(3), U128(10), msg);
+        testing_env!(get_context(accounts(3)).attached_deposit(ONE_NEAR).build());
+        contract.vote((1, accounts(2)), 1);
+        contract
+    }
+
+    #[test]
+    fn test_account_votes() {
+        let contract = setup();
+        let votes = contract.get_account_votes(accounts(3), 1, None, None);
+        assert_eq!(votes.len(), 2);
+        assert_eq!((votes[0].project_id.clone(), votes[0].votes), ((1, accounts(1)), 2));
+        assert_eq!(votes[0].next_vote_cost, U128(3 * ONE_NEAR / 10));
+        assert_eq!((votes[1].project_id.clone(), votes[1].votes), ((1, accounts(2)), 2));
+
+        let page = contract.get_account_votes(accounts(3), 1, Some(1), Some(1));
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].project_id, (1, accounts(2)));
+        assert!(contract.get_account_votes(accounts(4), 1, None, None).is_empty());
+    }
+
+    #[test]
+    fn test_account_contributions() {
+        let contract = setup();
+        let contributions = contract.get_account_contributions(accounts(3), 1, None, None);
+        assert_eq!(contributions[0].grants, U128(3 * ONE_NEAR / 10 * 95 / 100));
+        assert!(contributions[0].token_grants.is_empty());
+        assert_eq!(contributions[1].grants, U128(2 * ONE_NEAR / 10 * 95 / 100));
+        assert_eq!(contributions[1].token_grants, HashMap::from([(token(), U128(10))]));
+        assert_eq!(contributions[1].next_vote_cost, U128(3 * ONE_NEAR / 10));
+    }
+
+    #[test]
+    fn test_project_voters() {
+        let mut contract = setup();
+        testing_env!(get_context(accounts(4)).attached_deposit(ONE_NEAR).build());
+        contract.vote((1, accounts(1)), 1);
+
+        let voters = contract.get_project_voters((1, accounts(1)), None, None);
+        let voters: Vec<_> =
+            voters.into_iter().map(|voter| (voter.account_id, voter.votes)).collect();
+        assert_eq!(voters, vec![(accounts(3), 2), (accounts(4), 1)]);
+        let page = contract.get_project_voters((1, accounts(1)), Some(5), Some(1));
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].account_id, accounts(4));
+    }
+
+    #[test]
+    fn test_refund_removes_voter() {
+        let mut contract = setup();
+        testing_env!(get_context(accounts(0)).build());
+        contract.sudo_cancel_round(1);
+        testing_env!(get_context(accounts(3)).build());
+        contract.claim_refund(1, None);
+
+        assert!(contract.get_project_voters((1, accounts(1)), None, None).is_empty());
+        assert!(contract.get_account_votes(accounts(3), 1, None, None).is_empty());
+        assert_eq!(contract.get_project((1, accounts(1))).unwrap().total_votes, 0);
+    }
+
+    #[test]
+    fn test_vote_gas_is_flat() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = Contract::init();
+        register_accounts(&mut contract);
+        let rounds = 20;
+        for round_id in 1..=rounds {
+            contract.sudo_new_round(0, 100, None, None);
+            testing_env!(get_context(accounts(1)).build());
+            contract.new_project(Some(round_id), "a".into(), "b".into(), "c".into(), "d".into());
+            testing_env!(get_context(accounts(0)).build());
+            contract.sudo_approve_project((round_id, accounts(1)), None);
+        }
+
+        let mut gas = vec![];
+        for round_id in 1..=rounds {
+            testing_env!(get_context(accounts(3)).attached_deposit(ONE_NEAR).build());
+            let used_gas = env::used_gas();
+            contract.vote((round_id, accounts(1)), 1);
+            gas.push((env::used_gas() - used_gas).0);
+        }
+        // The first vote of the account is left out, it also creates its storage entries.
+        assert!(gas[rounds as usize - 1] <= gas[1] * 11 / 10);
+        assert_eq!(contract.get_account_votes(accounts(3), rounds, None, None).len(), 1);
+
+        // Nor does it grow with the projects the account voted for in the round.
+        let round_id = rounds + 1;
+        let owners: Vec<AccountId> =
+            (0..20).map(|index| format!("owner{}.near", index).parse().unwrap()).collect();
+        testing_env!(get_context(accounts(0)).attached_deposit(ONE_NEAR).build());
+        contract.sudo_new_round(0, 100, None, None);
+        for owner in &owners {
+            contract.storage_deposit(Some(owner.clone()), None);
+        }
+        for owner in &owners {
+            testing_env!(get_context(owner.clone()).build());
+            contract.new_project(Some(round_id), "a".into(), "b".into(), "c".into(), "d".into());
+            testing_env!(get_context(accounts(0)).build());
+            contract.sudo_approve_project((round_id, owner.clone()), None);
+        }
+        let vote = |contract: &mut Contract, voter: AccountId, owner: &AccountId| {
+            testing_env!(get_context(voter).attached_deposit(ONE_NEAR).build());
+            let used_gas = env::used_gas();
+            contract.vote((round_id, owner.clone()), 1);
+            (env::used_gas() - used_gas).0
+        };
+        for owner in &owners {
+            vote(&mut contract, accounts(3), owner);
+        }
+        vote(&mut contract, accounts(4), &owners[0]);
+        // Both voters already voted for the project, accounts(4) for no other one.
+        let one = vote(&mut contract, accounts(4), &owners[0]);
+        let many = vote(&mut contract, accounts(3), &owners[0]);
+        assert!(many <= one * 11 / 10);
+        assert_eq!(contract.get_account_votes(accounts(3), round_id, None, None).len(), 20);
+    }
+}
@@ -0,0 +1,40 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::UnorderedSet;
+use near_sdk::{env, near_bindgen, AccountId, BorshStorageKey, PanicOnDefault};
+
+/// Voter registry for local testing of round eligibility checks.
+/// The owner marks accounts as eligible, everyone else is not.
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
+pub struct Contract {
+    pub owner_id: AccountId,
+    eligible: UnorderedSet<AccountId>,
+}
+
+#[derive(BorshSerialize, BorshStorageKey)]
+enum StorageKey {
+    Eligible,
+}
+
+#[near_bindgen]
+impl Contract {
+    #[init]
+    pub fn new(owner_id: AccountId) -> Self {
+        assert!(!env::state_exists(), "Already initialized");
+        Self { owner_id, eligible: UnorderedSet::new(StorageKey::Eligible) }
+    }
+
+    /// Only can be called by owner.
+    pub fn set_eligible(&mut self, account_id: AccountId, eligible: bool) {
+        assert!(env::predecessor_account_id() == self.owner_id, "ERR_NOT_OWNER");
+        if eligible {
+            self.eligible.insert(&account_id);
+        } else {
+            self.eligible.remove(&account_id);
+        }
+    }
+
+    pub fn is_eligible(&self, account_id: AccountId) -> bool {
+        self.eligible.contains(&account_id)
+    }
+}
@@ -66,17 +66,56 @@ export type PublicKey = string;
 * Raw type for timestamp in nanoseconds
 */
 export type Timestamp = u64;
+/**
+* base58 string.
+*/
+export type Base58CryptoHash = string;
+export enum ChallengeStatus {
+  Open = "Open",
+  /**
+  * The project was disqualified.
+  */
+  Upheld = "Upheld",
+  Dismissed = "Dismissed",
+  /**
+  * The round was cancelled and the bond returned.
+  */
+  Cancelled = "Cancelled",
+}
+export interface Challenge {
+  challenger_id: AccountId;
+  reason: string;
+  bond: U128;
+  created_at: u32;
+  status: ChallengeStatus;
+}
 export interface Config {
   version: string;
   owner_id: AccountId;
+  treasury_id: AccountId;
   operators: AccountId[];
-  current_round?: Round;
+  last_round_id: RoundId;
+  active_rounds: Round[];
   fee_point: u32;
   default_duration: u32;
   default_vote_cost: U128;
+  default_review_duration: u32;
+  upgrade_delay: u32;
   fee_amount: U128;
   motivation: string;
 }
+export type MatchingStrategy = "Pairwise" | "Clr" | {PairwiseBounded: {m: u64}};
+/**
+* Code staged by `stage_upgrade`.
+*/
+export interface StagedUpgrade {
+  code_hash: Base58CryptoHash;
+  staged_at: u32;
+  /**
+  * Time after which `deploy_upgrade` can run.
+  */
+  deployable_at: u32;
+}
 export interface Project {
   name: string;
   description: string;
@@ -89,11 +128,119 @@ export interface Project {
   grants: U128;
   support_area: u64;
   withdrawn: U128;
+  beneficiary?: AccountId;
+  delegates: AccountId[];
+  token_grants: Record<AccountId, U128>;
+  token_withdrawn: Record<AccountId, U128>;
+  match_area: U128;
+  /**
+  * Sums of scaled `√c` and of their squares, kept for `Clr` rounds.
+  */
+  sqrt_sum: U128;
+  square_sum: U128;
+  status: ProjectStatus;
+  /**
+  * Reason given by the operator who reviewed the project.
+  */
+  review_reason?: string;
+  /**
+  * Position among projects of the round, which `sudo_tally_round` goes through in order.
+  */
+  index: u32;
+}
+/**
+* Review state of a project application, only approved projects take votes.
+*/
+export enum ProjectStatus {
+  Pending = "Pending",
+  Approved = "Approved",
+  Rejected = "Rejected",
+  /**
+  * Removed from matching after a challenge, voters can claim their grants back.
+  */
+  Disqualified = "Disqualified",
 }
 export type ProjectId = [RoundId, AccountId];
+/**
+* Project metadata replaced by `update_project`.
+*/
+export interface ProjectRevision {
+  name: string;
+  description: string;
+  external_url: string;
+  image: string;
+  replaced_at: u32;
+}
+/**
+* Orders of `list_projects_v2`, projects with bigger values go first.
+*/
+export enum ProjectSort {
+  TotalVotes = "TotalVotes",
+  Grants = "Grants",
+  /**
+  * Share of the matching pool, by support area or match area depending on the strategy.
+  */
+  EstimatedMatch = "EstimatedMatch",
+  CreatedAt = "CreatedAt",
+}
+/**
+* Position of a project in a ranking, its sort value and owner.
+*/
+export type ProjectCursor = [U128, AccountId];
+export interface ProjectPage {
+  projects: Project[];
+  /**
+  * Pass as `from_key` to get the next page, missing on the last page.
+  */
+  next_key?: ProjectCursor;
+}
+/**
+* Outcome of a project written when its round is finished, payouts only read it.
+*/
+export interface RoundResult {
+  project_id: ProjectId;
+  /**
+  * Paid directly by voters in NEAR after the platform fee.
+  */
+  grants: U128;
+  /**
+  * Part of the NEAR matching pool.
+  */
+  matched: U128;
+  /**
+  * Share of the NEAR matching pool in basis points.
+  */
+  share_point: u32;
+  /**
+  * Place in the round by estimated match, starting from 1.
+  */
+  rank: u32;
+  token_grants: Record<AccountId, U128>;
+  token_matched: Record<AccountId, U128>;
+}
+/**
+* Where undistributed matching funds of a finished round go.
+*/
+export type RolloverTarget = {Round: {round_id: RoundId}} | "Reserve";
+/**
+* Matching funds a finished round did not distribute, in NEAR and in tokens.
+*/
+export interface Rollover {
+  target: RolloverTarget;
+  amount: U128;
+  token_amounts: Record<AccountId, U128>;
+}
 export enum RoundStatus {
   Active = "Active",
   Finished = "Finished",
+  /**
+  * Aborted round, voters and donors take their money back with `claim_refund`.
+  */
+  Cancelled = "Cancelled",
+  /**
+  * Results are being written by `sudo_finish_round`, payouts wait for `Finished`.
+  */
+  Finishing = "Finishing",
 }
 export interface Round {
   created_at: u32;
@@ -106,13 +253,266 @@ export interface Round {
   vote_cost: U128;
   projects: u32;
   status: RoundStatus;
+  /**
+  * Fungible tokens accepted for votes and donations with their vote cost.
+  */
+  accepted_tokens: Record<AccountId, U128>;
+  token_pools: Record<AccountId, TokenPool>;
+  matching: MatchingStrategy;
+  /**
+  * Total match area of projects for strategies other than `Pairwise`.
+  */
+  match_area: U128;
+  tallied_projects: u32;
+  /**
+  * Position in the round projects `sudo_tally_round` continues from.
+  */
+  tally_cursor: u32;
+  /**
+  * Next pair of voters of the project at `tally_cursor` and the area of pairs before it.
+  */
+  tally_pair: [u32, u32];
+  tally_area: U128;
+  /**
+  * Contract confirming that voters are eligible, see `Registry`.
+  */
+  registry_id?: AccountId;
+  /**
+  * Undistributed matching funds moved out by `sudo_rollover_round`.
+  */
+  rollover?: Rollover;
+  /**
+  * Rounds whose undistributed matching funds were added to this round.
+  */
+  rolled_over_from: RoundId[];
+  params: RoundParams;
+  /**
+  * Projects with challenges waiting for resolution, the round cannot be finished
+  * until there are none.
+  */
+  open_challenges: ProjectId[];
+  /**
+  * Matching funds which are not donations, they go back to the reserve on cancel.
+  */
+  funded: U128;
+  token_funded: Record<AccountId, U128>;
+  /**
+  * Results written by `sudo_finish_round`, the rank of the last one.
+  */
+  ranked_projects: u32;
+  /**
+  * Last project of the estimated match ranking `sudo_finish_round` went through.
+  */
+  rank_cursor?: ProjectCursor;
+  /**
+  * Matched to projects by the results written so far, the rest can be rolled over.
+  */
+  matched: U128;
+  token_matched: Record<AccountId, U128>;
+  /**
+  * Platform fees taken in the round, collected once it finishes and refunded on cancel.
+  */
+  fees: U128;
+  token_fees: Record<AccountId, U128>;
+}
+export interface TokenPool {
+  pure_support_pool: U128;
+  support_pool: U128;
 }
 export type RoundId = u64;
+/**
+* Timeline of a round around its voting window from `start_at` to `end_at`.
+*/
+export interface RoundPhases {
+  application_start_at: u32;
+  /**
+  * Applications close before voting starts.
+  */
+  application_end_at: u32;
+  /**
+  * Review and challenges go on from the end of voting until payouts start.
+  */
+  payout_at: u32;
+}
+export enum RoundPhase {
+  /**
+  * Before applications open or between applications and voting.
+  */
+  Upcoming = "Upcoming",
+  Application = "Application",
+  Voting = "Voting",
+  Review = "Review",
+  Payout = "Payout",
+  Cancelled = "Cancelled",
+}
+/**
+* Settings of a round given at creation, vote cost and fee default to the contract config.
+*/
+export interface RoundParams {
+  vote_cost?: U128;
+  /**
+  * Platform fee in basis points.
+  */
+  fee_point?: u32;
+  /**
+  * Most votes one account can give across projects of the round.
+  */
+  max_votes_per_voter?: u64;
+  /**
+  * Most projects that can be approved in the round.
+  */
+  max_projects?: u32;
+  /**
+  * Most NEAR matched to one project, the rest is left for rollover.
+  */
+  matching_cap?: U128;
+  /**
+  * Without phases projects apply until voting ends and payouts start after the review.
+  */
+  phases?: RoundPhases;
+  /**
+  * Seconds between the end of voting and payouts when there are no `phases`.
+  */
+  review_duration?: u32;
+  /**
+  * Most accounts voting for one project, set for `PairwiseBounded` rounds.
+  */
+  max_voters_per_project?: u32;
+}
+/**
+* `msg` of `ft_transfer_call` to the grant contract.
+*/
+export type FtMessage = {Vote: {project_id: ProjectId; votes: u64}} | {Donate: {round_id: RoundId}};
+export interface AccountVote {
+  project_id: ProjectId;
+  votes: u64;
+  /**
+  * NEAR paid for one more vote for the project.
+  */
+  next_vote_cost: U128;
+}
+export interface AccountContribution {
+  project_id: ProjectId;
+  votes: u64;
+  /**
+  * Paid for votes in NEAR after the platform fee.
+  */
+  grants: U128;
+  token_grants: Record<AccountId, U128>;
+  next_vote_cost: U128;
+}
+export interface ProjectVoter {
+  account_id: AccountId;
+  votes: u64;
+}
+export interface StorageBalance {
+  total: U128;
+  available: U128;
+}
+export interface StorageBalanceBounds {
+  min: U128;
+  max?: U128;
+}
 
 export class Contract {
   
   constructor(public account: Account, public readonly contractId: string){}
   
+  /**
+  * Contest an approved project during the review phase of its round,
+  * `CHALLENGE_BOND` must be attached.
+  */
+  async challenge_project(args: {
+    project_id: ProjectId;
+    reason: string;
+  }, options?: ChangeMethodOptions): Promise<Challenge> {
+    return providers.getTransactionLastResult(await this.challenge_projectRaw(args, options));
+  }
+  /**
+  * Contest an approved project during the review phase of its round,
+  * `CHALLENGE_BOND` must be attached.
+  */
+  challenge_projectRaw(args: {
+    project_id: ProjectId;
+    reason: string;
+  }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
+    return this.account.functionCall({contractId: this.contractId, methodName: "challenge_project", args, ...options});
+  }
+  /**
+  * Contest an approved project during the review phase of its round,
+  * `CHALLENGE_BOND` must be attached.
+  */
+  challenge_projectTx(args: {
+    project_id: ProjectId;
+    reason: string;
+  }, options?: ChangeMethodOptions): transactions.Action {
+    return transactions.functionCall("challenge_project", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
+  }
+  /**
+  * Disqualify the challenged project and return the bond, or dismiss the challenge
+  * and keep the bond as platform fee. Only can be called by owner or operator.
+  */
+  async sudo_resolve_challenge(args: {
+    project_id: ProjectId;
+    disqualify: boolean;
+  }, options?: ChangeMethodOptions): Promise<Challenge> {
+    return providers.getTransactionLastResult(await this.sudo_resolve_challengeRaw(args, options));
+  }
+  /**
+  * Disqualify the challenged project and return the bond, or dismiss the challenge
+  * and keep the bond as platform fee. Only can be called by owner or operator.
+  */
+  sudo_resolve_challengeRaw(args: {
+    project_id: ProjectId;
+    disqualify: boolean;
+  }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
+    return this.account.functionCall({contractId: this.contractId, methodName: "sudo_resolve_challenge", args, ...options});
+  }
+  /**
+  * Disqualify the challenged project and return the bond, or dismiss the challenge
+  * and keep the bond as platform fee. Only can be called by owner or operator.
+  */
+  sudo_resolve_challengeTx(args: {
+    project_id: ProjectId;
+    disqualify: boolean;
+  }, options?: ChangeMethodOptions): transactions.Action {
+    return transactions.functionCall("sudo_resolve_challenge", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
+  }
+  get_challenge(args: {
+    project_id: ProjectId;
+  }, options?: ViewFunctionOptions): Promise<Challenge | null> {
+    return this.account.viewFunction(this.contractId, "get_challenge", args, options);
+  }
+  /**
+  * Take back votes for a disqualified project, paid in NEAR or in `token_id`.
+  * Platform fees are not refunded.
+  */
+  async claim_grant_refund(args: {
+    project_id: ProjectId;
+    token_id?: AccountId;
+  }, options?: ChangeMethodOptions): Promise<void> {
+    return providers.getTransactionLastResult(await this.claim_grant_refundRaw(args, options));
+  }
+  /**
+  * Take back votes for a disqualified project, paid in NEAR or in `token_id`.
+  * Platform fees are not refunded.
+  */
+  claim_grant_refundRaw(args: {
+    project_id: ProjectId;
+    token_id?: AccountId;
+  }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
+    return this.account.functionCall({contractId: this.contractId, methodName: "claim_grant_refund", args, ...options});
+  }
+  /**
+  * Take back votes for a disqualified project, paid in NEAR or in `token_id`.
+  * Platform fees are not refunded.
+  */
+  claim_grant_refundTx(args: {
+    project_id: ProjectId;
+    token_id?: AccountId;
+  }, options?: ChangeMethodOptions): transactions.Action {
+    return transactions.functionCall("claim_grant_refund", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
+  }
   get_config(args = {}, options?: ViewFunctionOptions): Promise<Config> {
     return this.account.viewFunction(this.contractId, "get_config", args, options);
   }
@@ -120,6 +520,7 @@ export class Contract {
     fee_point?: u32;
     default_duration?: u32;
     default_vote_cost?: U128;
+    default_review_duration?: u32;
   }, options?: ChangeMethodOptions): Promise<void> {
     return providers.getTransactionLastResult(await this.sudo_configRaw(args, options));
   }
@@ -127,6 +528,7 @@ export class Contract {
     fee_point?: u32;
     default_duration?: u32;
     default_vote_cost?: U128;
+    default_review_duration?: u32;
   }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
     return this.account.functionCall({contractId: this.contractId, methodName: "sudo_config", args, ...options});
   }
@@ -134,9 +536,120 @@ export class Contract {
     fee_point?: u32;
     default_duration?: u32;
     default_vote_cost?: U128;
+    default_review_duration?: u32;
   }, options?: ChangeMethodOptions): transactions.Action {
     return transactions.functionCall("sudo_config", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
   }
+  /**
+  * Send collected platform fees in NEAR, or in `token_id` if given,
+  * to `receiver_id` or to the treasury. Only can be called by owner.
+  */
+  async sudo_withdraw_fees(args: {
+    amount: U128;
+    receiver_id?: AccountId;
+    token_id?: AccountId;
+  }, options?: ChangeMethodOptions): Promise<U128> {
+    return providers.getTransactionLastResult(await this.sudo_withdraw_feesRaw(args, options));
+  }
+  /**
+  * Send collected platform fees in NEAR, or in `token_id` if given,
+  * to `receiver_id` or to the treasury. Only can be called by owner.
+  */
+  sudo_withdraw_feesRaw(args: {
+    amount: U128;
+    receiver_id?: AccountId;
+    token_id?: AccountId;
+  }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
+    return this.account.functionCall({contractId: this.contractId, methodName: "sudo_withdraw_fees", args, ...options});
+  }
+  /**
+  * Send collected platform fees in NEAR, or in `token_id` if given,
+  * to `receiver_id` or to the treasury. Only can be called by owner.
+  */
+  sudo_withdraw_feesTx(args: {
+    amount: U128;
+    receiver_id?: AccountId;
+    token_id?: AccountId;
+  }, options?: ChangeMethodOptions): transactions.Action {
+    return transactions.functionCall("sudo_withdraw_fees", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
+  }
+  /**
+  * Collected platform fees in NEAR or in `token_id`.
+  */
+  get_fee_amount(args: {
+    token_id?: AccountId;
+  }, options?: ViewFunctionOptions): Promise<U128> {
+    return this.account.viewFunction(this.contractId, "get_fee_amount", args, options);
+  }
+  /**
+  * Scores up to `limit` more projects of a `PairwiseBounded` round after voting ended, skipping
+  * projects which are not approved. One call scores at most `TALLY_PAIRS_PER_CALL` pairs
+  * of voters and continues a project where the last call stopped.
+  * Call until `tallied_projects` reaches `projects`, matching is paid out after that.
+  */
+  async sudo_tally_round(args: {
+    round_id: RoundId;
+    limit?: u32;
+  }, options?: ChangeMethodOptions): Promise<Round> {
+    return providers.getTransactionLastResult(await this.sudo_tally_roundRaw(args, options));
+  }
+  /**
+  * Scores up to `limit` more projects of a `PairwiseBounded` round after voting ended, skipping
+  * projects which are not approved. One call scores at most `TALLY_PAIRS_PER_CALL` pairs
+  * of voters and continues a project where the last call stopped.
+  * Call until `tallied_projects` reaches `projects`, matching is paid out after that.
+  */
+  sudo_tally_roundRaw(args: {
+    round_id: RoundId;
+    limit?: u32;
+  }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
+    return this.account.functionCall({contractId: this.contractId, methodName: "sudo_tally_round", args, ...options});
+  }
+  /**
+  * Scores up to `limit` more projects of a `PairwiseBounded` round after voting ended, skipping
+  * projects which are not approved. One call scores at most `TALLY_PAIRS_PER_CALL` pairs
+  * of voters and continues a project where the last call stopped.
+  * Call until `tallied_projects` reaches `projects`, matching is paid out after that.
+  */
+  sudo_tally_roundTx(args: {
+    round_id: RoundId;
+    limit?: u32;
+  }, options?: ChangeMethodOptions): transactions.Action {
+    return transactions.functionCall("sudo_tally_round", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
+  }
+  /**
+  * Moves at most `limit` entries of the old state, `MIGRATE_ENTRIES_PER_CALL` by default.
+  * Call until it returns true, calls which read old state fail with `ERR_MIGRATION_PENDING`
+  * meanwhile.
+  */
+  async sudo_migrate(args: {
+    limit?: u32;
+  }, options?: ChangeMethodOptions): Promise<boolean> {
+    return providers.getTransactionLastResult(await this.sudo_migrateRaw(args, options));
+  }
+  /**
+  * Moves at most `limit` entries of the old state, `MIGRATE_ENTRIES_PER_CALL` by default.
+  * Call until it returns true, calls which read old state fail with `ERR_MIGRATION_PENDING`
+  * meanwhile.
+  */
+  sudo_migrateRaw(args: {
+    limit?: u32;
+  }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
+    return this.account.functionCall({contractId: this.contractId, methodName: "sudo_migrate", args, ...options});
+  }
+  /**
+  * Moves at most `limit` entries of the old state, `MIGRATE_ENTRIES_PER_CALL` by default.
+  * Call until it returns true, calls which read old state fail with `ERR_MIGRATION_PENDING`
+  * meanwhile.
+  */
+  sudo_migrateTx(args: {
+    limit?: u32;
+  }, options?: ChangeMethodOptions): transactions.Action {
+    return transactions.functionCall("sudo_migrate", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
+  }
+  is_migrated(args = {}, options?: ViewFunctionOptions): Promise<boolean> {
+    return this.account.viewFunction(this.contractId, "is_migrated", args, options);
+  }
   async set_owner(args: {
     new_owner_id: AccountId;
   }, options?: ChangeMethodOptions): Promise<void> {
@@ -153,6 +666,30 @@ export class Contract {
     return transactions.functionCall("set_owner", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
   }
   /**
+  * Set account that receives platform fees. Only can be called by owner.
+  */
+  async set_treasury(args: {
+    treasury_id: AccountId;
+  }, options?: ChangeMethodOptions): Promise<void> {
+    return providers.getTransactionLastResult(await this.set_treasuryRaw(args, options));
+  }
+  /**
+  * Set account that receives platform fees. Only can be called by owner.
+  */
+  set_treasuryRaw(args: {
+    treasury_id: AccountId;
+  }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
+    return this.account.functionCall({contractId: this.contractId, methodName: "set_treasury", args, ...options});
+  }
+  /**
+  * Set account that receives platform fees. Only can be called by owner.
+  */
+  set_treasuryTx(args: {
+    treasury_id: AccountId;
+  }, options?: ChangeMethodOptions): transactions.Action {
+    return transactions.functionCall("set_treasury", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
+  }
+  /**
   * Get operators
   */
   operators(args = {}, options?: ViewFunctionOptions): Promise<AccountId[]> {
@@ -206,23 +743,113 @@ export class Contract {
   }, options?: ChangeMethodOptions): transactions.Action {
     return transactions.functionCall("remove_operators", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
   }
-  async new_project(args: {
-    name: string;
-    description: string;
-    external_url: string;
-    image: string;
-  }, options?: ChangeMethodOptions): Promise<Project> {
-    return providers.getTransactionLastResult(await this.new_projectRaw(args, options));
+  /**
+  * Set seconds between staging code and deploying it. Only can be called by owner.
+  * The delay only can go up, lowering it takes an upgrade which waits the current delay.
+  */
+  async set_upgrade_delay(args: {
+    upgrade_delay: u32;
+  }, options?: ChangeMethodOptions): Promise<void> {
+    return providers.getTransactionLastResult(await this.set_upgrade_delayRaw(args, options));
   }
-  new_projectRaw(args: {
-    name: string;
-    description: string;
-    external_url: string;
-    image: string;
+  /**
+  * Set seconds between staging code and deploying it. Only can be called by owner.
+  * The delay only can go up, lowering it takes an upgrade which waits the current delay.
+  */
+  set_upgrade_delayRaw(args: {
+    upgrade_delay: u32;
+  }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
+    return this.account.functionCall({contractId: this.contractId, methodName: "set_upgrade_delay", args, ...options});
+  }
+  /**
+  * Set seconds between staging code and deploying it. Only can be called by owner.
+  * The delay only can go up, lowering it takes an upgrade which waits the current delay.
+  */
+  set_upgrade_delayTx(args: {
+    upgrade_delay: u32;
+  }, options?: ChangeMethodOptions): transactions.Action {
+    return transactions.functionCall("set_upgrade_delay", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
+  }
+  /**
+  * Store new contract code to be deployed after the upgrade delay,
+  * replacing code staged before. Only can be called by owner.
+  */
+  async stage_upgrade(args: {
+    code: Base64VecU8;
+  }, options?: ChangeMethodOptions): Promise<StagedUpgrade> {
+    return providers.getTransactionLastResult(await this.stage_upgradeRaw(args, options));
+  }
+  /**
+  * Store new contract code to be deployed after the upgrade delay,
+  * replacing code staged before. Only can be called by owner.
+  */
+  stage_upgradeRaw(args: {
+    code: Base64VecU8;
+  }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
+    return this.account.functionCall({contractId: this.contractId, methodName: "stage_upgrade", args, ...options});
+  }
+  /**
+  * Store new contract code to be deployed after the upgrade delay,
+  * replacing code staged before. Only can be called by owner.
+  */
+  stage_upgradeTx(args: {
+    code: Base64VecU8;
+  }, options?: ChangeMethodOptions): transactions.Action {
+    return transactions.functionCall("stage_upgrade", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
+  }
+  /**
+  * Deploy staged code and call `migrate` on it. Only can be called by owner.
+  */
+  async deploy_upgrade(args = {}, options?: ChangeMethodOptions): Promise<void> {
+    return providers.getTransactionLastResult(await this.deploy_upgradeRaw(args, options));
+  }
+  /**
+  * Deploy staged code and call `migrate` on it. Only can be called by owner.
+  */
+  deploy_upgradeRaw(args = {}, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
+    return this.account.functionCall({contractId: this.contractId, methodName: "deploy_upgrade", args, ...options});
+  }
+  /**
+  * Deploy staged code and call `migrate` on it. Only can be called by owner.
+  */
+  deploy_upgradeTx(args = {}, options?: ChangeMethodOptions): transactions.Action {
+    return transactions.functionCall("deploy_upgrade", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
+  }
+  get_staged_upgrade(args = {}, options?: ViewFunctionOptions): Promise<StagedUpgrade | null> {
+    return this.account.viewFunction(this.contractId, "get_staged_upgrade", args, options);
+  }
+  /**
+  * Applies to the round, the scheduled one taking applications if not given,
+  * the project waits for review.
+  */
+  async new_project(args: {
+    round_id?: RoundId;
+    name: string;
+    description: string;
+    external_url: string;
+    image: string;
+  }, options?: ChangeMethodOptions): Promise<Project> {
+    return providers.getTransactionLastResult(await this.new_projectRaw(args, options));
+  }
+  /**
+  * Applies to the round, the scheduled one taking applications if not given,
+  * the project waits for review.
+  */
+  new_projectRaw(args: {
+    round_id?: RoundId;
+    name: string;
+    description: string;
+    external_url: string;
+    image: string;
   }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
     return this.account.functionCall({contractId: this.contractId, methodName: "new_project", args, ...options});
   }
+  /**
+  * Applies to the round, the scheduled one taking applications if not given,
+  * the project waits for review.
+  */
   new_projectTx(args: {
+    round_id?: RoundId;
     name: string;
     description: string;
     external_url: string;
@@ -230,37 +857,222 @@ export class Contract {
   }, options?: ChangeMethodOptions): transactions.Action {
     return transactions.functionCall("new_project", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
   }
+  /**
+  * Returns withdrawable and total granted amounts in NEAR or in `token_id`.
+  */
   grant_for(args: {
     project_id: ProjectId;
-  }, options?: ViewFunctionOptions): Promise<U128> {
+    token_id?: AccountId;
+  }, options?: ViewFunctionOptions): Promise<[U128, U128]> {
     return this.account.viewFunction(this.contractId, "grant_for", args, options);
   }
+  /**
+  * Withdraw NEAR, or `token_id` if given, to the project payout account.
+  */
   async withdraw(args: {
     project_id: ProjectId;
     amount: U128;
-  }, options?: ChangeMethodOptions): Promise<void> {
+    token_id?: AccountId;
+  }, options?: ChangeMethodOptions): Promise<U128> {
     return providers.getTransactionLastResult(await this.withdrawRaw(args, options));
   }
+  /**
+  * Withdraw NEAR, or `token_id` if given, to the project payout account.
+  */
   withdrawRaw(args: {
     project_id: ProjectId;
     amount: U128;
+    token_id?: AccountId;
   }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
     return this.account.functionCall({contractId: this.contractId, methodName: "withdraw", args, ...options});
   }
+  /**
+  * Withdraw NEAR, or `token_id` if given, to the project payout account.
+  */
   withdrawTx(args: {
     project_id: ProjectId;
     amount: U128;
+    token_id?: AccountId;
   }, options?: ChangeMethodOptions): transactions.Action {
     return transactions.functionCall("withdraw", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
   }
+  /**
+  * Set account that receives payouts, `None` pays to the project owner.
+  * Only can be called by project owner.
+  */
+  async set_project_beneficiary(args: {
+    project_id: ProjectId;
+    beneficiary?: AccountId;
+  }, options?: ChangeMethodOptions): Promise<Project> {
+    return providers.getTransactionLastResult(await this.set_project_beneficiaryRaw(args, options));
+  }
+  /**
+  * Set account that receives payouts, `None` pays to the project owner.
+  * Only can be called by project owner.
+  */
+  set_project_beneficiaryRaw(args: {
+    project_id: ProjectId;
+    beneficiary?: AccountId;
+  }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
+    return this.account.functionCall({contractId: this.contractId, methodName: "set_project_beneficiary", args, ...options});
+  }
+  /**
+  * Set account that receives payouts, `None` pays to the project owner.
+  * Only can be called by project owner.
+  */
+  set_project_beneficiaryTx(args: {
+    project_id: ProjectId;
+    beneficiary?: AccountId;
+  }, options?: ChangeMethodOptions): transactions.Action {
+    return transactions.functionCall("set_project_beneficiary", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
+  }
+  /**
+  * Set accounts allowed to call `withdraw` besides the owner.
+  * Only can be called by project owner.
+  */
+  async set_project_delegates(args: {
+    project_id: ProjectId;
+    delegates: AccountId[];
+  }, options?: ChangeMethodOptions): Promise<Project> {
+    return providers.getTransactionLastResult(await this.set_project_delegatesRaw(args, options));
+  }
+  /**
+  * Set accounts allowed to call `withdraw` besides the owner.
+  * Only can be called by project owner.
+  */
+  set_project_delegatesRaw(args: {
+    project_id: ProjectId;
+    delegates: AccountId[];
+  }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
+    return this.account.functionCall({contractId: this.contractId, methodName: "set_project_delegates", args, ...options});
+  }
+  /**
+  * Set accounts allowed to call `withdraw` besides the owner.
+  * Only can be called by project owner.
+  */
+  set_project_delegatesTx(args: {
+    project_id: ProjectId;
+    delegates: AccountId[];
+  }, options?: ChangeMethodOptions): transactions.Action {
+    return transactions.functionCall("set_project_delegates", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
+  }
+  /**
+  * Approve a pending project, it takes votes and gets matched from now on.
+  * Only can be called by owner or operators while the round is open.
+  */
+  async sudo_approve_project(args: {
+    project_id: ProjectId;
+    reason?: string;
+  }, options?: ChangeMethodOptions): Promise<Project> {
+    return providers.getTransactionLastResult(await this.sudo_approve_projectRaw(args, options));
+  }
+  /**
+  * Approve a pending project, it takes votes and gets matched from now on.
+  * Only can be called by owner or operators while the round is open.
+  */
+  sudo_approve_projectRaw(args: {
+    project_id: ProjectId;
+    reason?: string;
+  }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
+    return this.account.functionCall({contractId: this.contractId, methodName: "sudo_approve_project", args, ...options});
+  }
+  /**
+  * Approve a pending project, it takes votes and gets matched from now on.
+  * Only can be called by owner or operators while the round is open.
+  */
+  sudo_approve_projectTx(args: {
+    project_id: ProjectId;
+    reason?: string;
+  }, options?: ChangeMethodOptions): transactions.Action {
+    return transactions.functionCall("sudo_approve_project", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
+  }
+  /**
+  * Reject a pending project. Only can be called by owner or operators.
+  */
+  async sudo_reject_project(args: {
+    project_id: ProjectId;
+    reason: string;
+  }, options?: ChangeMethodOptions): Promise<Project> {
+    return providers.getTransactionLastResult(await this.sudo_reject_projectRaw(args, options));
+  }
+  /**
+  * Reject a pending project. Only can be called by owner or operators.
+  */
+  sudo_reject_projectRaw(args: {
+    project_id: ProjectId;
+    reason: string;
+  }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
+    return this.account.functionCall({contractId: this.contractId, methodName: "sudo_reject_project", args, ...options});
+  }
+  /**
+  * Reject a pending project. Only can be called by owner or operators.
+  */
+  sudo_reject_projectTx(args: {
+    project_id: ProjectId;
+    reason: string;
+  }, options?: ChangeMethodOptions): transactions.Action {
+    return transactions.functionCall("sudo_reject_project", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
+  }
+  /**
+  * Update project metadata while its round is open, previous values are kept
+  * in the revision history. Only can be called by project owner.
+  */
+  async update_project(args: {
+    project_id: ProjectId;
+    name?: string;
+    description?: string;
+    external_url?: string;
+    image?: string;
+  }, options?: ChangeMethodOptions): Promise<Project> {
+    return providers.getTransactionLastResult(await this.update_projectRaw(args, options));
+  }
+  /**
+  * Update project metadata while its round is open, previous values are kept
+  * in the revision history. Only can be called by project owner.
+  */
+  update_projectRaw(args: {
+    project_id: ProjectId;
+    name?: string;
+    description?: string;
+    external_url?: string;
+    image?: string;
+  }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
+    return this.account.functionCall({contractId: this.contractId, methodName: "update_project", args, ...options});
+  }
+  /**
+  * Update project metadata while its round is open, previous values are kept
+  * in the revision history. Only can be called by project owner.
+  */
+  update_projectTx(args: {
+    project_id: ProjectId;
+    name?: string;
+    description?: string;
+    external_url?: string;
+    image?: string;
+  }, options?: ChangeMethodOptions): transactions.Action {
+    return transactions.functionCall("update_project", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
+  }
+  /**
+  * Past metadata of the project, oldest first.
+  */
+  get_project_revisions(args: {
+    project_id: ProjectId;
+  }, options?: ViewFunctionOptions): Promise<ProjectRevision[]> {
+    return this.account.viewFunction(this.contractId, "get_project_revisions", args, options);
+  }
   get_project(args: {
     project_id: ProjectId;
   }, options?: ViewFunctionOptions): Promise<Project | null> {
     return this.account.viewFunction(this.contractId, "get_project", args, options);
   }
+  /**
+  * Projects of the round, only ones with `status` if given.
+  */
   list_projects(args: {
+    round_id: RoundId;
     limit?: u32;
     offset?: u32;
+    status?: ProjectStatus;
   }, options?: ViewFunctionOptions): Promise<Project[]> {
     return this.account.viewFunction(this.contractId, "list_projects", args, options);
   }
@@ -271,135 +1083,1398 @@ export class Contract {
   }, options?: ViewFunctionOptions): Promise<Project[]> {
     return this.account.viewFunction(this.contractId, "projects_for_owner", args, options);
   }
+  /**
+  * Vote for the project paying with attached NEAR, the excess is refunded.
+  * When the round has a registry, the vote is applied once the registry confirms
+  * that the voter is eligible and resolves to `null` with a full refund otherwise.
+  */
   async vote(args: {
     project_id: ProjectId;
     votes: u64;
   }, options?: ChangeMethodOptions): Promise<Project> {
     return providers.getTransactionLastResult(await this.voteRaw(args, options));
   }
+  /**
+  * Vote for the project paying with attached NEAR, the excess is refunded.
+  * When the round has a registry, the vote is applied once the registry confirms
+  * that the voter is eligible and resolves to `null` with a full refund otherwise.
+  */
   voteRaw(args: {
     project_id: ProjectId;
     votes: u64;
   }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
     return this.account.functionCall({contractId: this.contractId, methodName: "vote", args, ...options});
   }
+  /**
+  * Vote for the project paying with attached NEAR, the excess is refunded.
+  * When the round has a registry, the vote is applied once the registry confirms
+  * that the voter is eligible and resolves to `null` with a full refund otherwise.
+  */
   voteTx(args: {
     project_id: ProjectId;
     votes: u64;
   }, options?: ChangeMethodOptions): transactions.Action {
     return transactions.functionCall("vote", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
   }
+  /**
+  * Projects of the round ordered by `sort_by`, starting after `from_key`.
+  * Every page costs the same whatever its depth.
+  */
+  list_projects_v2(args: {
+    round_id: RoundId;
+    sort_by: ProjectSort;
+    from_key?: ProjectCursor;
+    limit?: u32;
+  }, options?: ViewFunctionOptions): Promise<ProjectPage> {
+    return this.account.viewFunction(this.contractId, "list_projects_v2", args, options);
+  }
+  /**
+  * Abort an unfinished round, nothing is paid out to projects and voters and donors
+  * can claim refunds including platform fees. Funds added by `sudo_fund_round` or a rollover go to the reserve
+  * and bonds of open challenges go back. Only can be called by owner.
+  */
+  async sudo_cancel_round(args: {
+    round_id: RoundId;
+  }, options?: ChangeMethodOptions): Promise<Round> {
+    return providers.getTransactionLastResult(await this.sudo_cancel_roundRaw(args, options));
+  }
+  /**
+  * Abort an unfinished round, nothing is paid out to projects and voters and donors
+  * can claim refunds including platform fees. Funds added by `sudo_fund_round` or a rollover go to the reserve
+  * and bonds of open challenges go back. Only can be called by owner.
+  */
+  sudo_cancel_roundRaw(args: {
+    round_id: RoundId;
+  }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
+    return this.account.functionCall({contractId: this.contractId, methodName: "sudo_cancel_round", args, ...options});
+  }
+  /**
+  * Abort an unfinished round, nothing is paid out to projects and voters and donors
+  * can claim refunds including platform fees. Funds added by `sudo_fund_round` or a rollover go to the reserve
+  * and bonds of open challenges go back. Only can be called by owner.
+  */
+  sudo_cancel_roundTx(args: {
+    round_id: RoundId;
+  }, options?: ChangeMethodOptions): transactions.Action {
+    return transactions.functionCall("sudo_cancel_round", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
+  }
+  /**
+  * Take back votes and donations to a cancelled round with their platform fees,
+  * paid in NEAR or in `token_id`.
+  */
+  async claim_refund(args: {
+    round_id: RoundId;
+    token_id?: AccountId;
+  }, options?: ChangeMethodOptions): Promise<void> {
+    return providers.getTransactionLastResult(await this.claim_refundRaw(args, options));
+  }
+  /**
+  * Take back votes and donations to a cancelled round with their platform fees,
+  * paid in NEAR or in `token_id`.
+  */
+  claim_refundRaw(args: {
+    round_id: RoundId;
+    token_id?: AccountId;
+  }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
+    return this.account.functionCall({contractId: this.contractId, methodName: "claim_refund", args, ...options});
+  }
+  /**
+  * Take back votes and donations to a cancelled round with their platform fees,
+  * paid in NEAR or in `token_id`.
+  */
+  claim_refundTx(args: {
+    round_id: RoundId;
+    token_id?: AccountId;
+  }, options?: ChangeMethodOptions): transactions.Action {
+    return transactions.functionCall("claim_refund", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
+  }
+  /**
+  * Set the registry that confirms voters of the round, `None` lets everyone vote.
+  */
+  async sudo_set_round_registry(args: {
+    round_id: RoundId;
+    registry_id?: AccountId;
+  }, options?: ChangeMethodOptions): Promise<Round> {
+    return providers.getTransactionLastResult(await this.sudo_set_round_registryRaw(args, options));
+  }
+  /**
+  * Set the registry that confirms voters of the round, `None` lets everyone vote.
+  */
+  sudo_set_round_registryRaw(args: {
+    round_id: RoundId;
+    registry_id?: AccountId;
+  }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
+    return this.account.functionCall({contractId: this.contractId, methodName: "sudo_set_round_registry", args, ...options});
+  }
+  /**
+  * Set the registry that confirms voters of the round, `None` lets everyone vote.
+  */
+  sudo_set_round_registryTx(args: {
+    round_id: RoundId;
+    registry_id?: AccountId;
+  }, options?: ChangeMethodOptions): transactions.Action {
+    return transactions.functionCall("sudo_set_round_registry", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
+  }
+  /**
+  * Results of a finished round ordered by rank.
+  */
+  get_round_results(args: {
+    round_id: RoundId;
+    limit?: u32;
+    offset?: u32;
+  }, options?: ViewFunctionOptions): Promise<RoundResult[]> {
+    return this.account.viewFunction(this.contractId, "get_round_results", args, options);
+  }
+  get_round_result(args: {
+    project_id: ProjectId;
+  }, options?: ViewFunctionOptions): Promise<RoundResult | null> {
+    return this.account.viewFunction(this.contractId, "get_round_result", args, options);
+  }
+  /**
+  * Move matching funds of a finished round that no project can withdraw, because nobody
+  * co-voted or because of rounding, to the pool of an active round or to the reserve.
+  */
+  async sudo_rollover_round(args: {
+    round_id: RoundId;
+    target: RolloverTarget;
+  }, options?: ChangeMethodOptions): Promise<Round> {
+    return providers.getTransactionLastResult(await this.sudo_rollover_roundRaw(args, options));
+  }
+  /**
+  * Move matching funds of a finished round that no project can withdraw, because nobody
+  * co-voted or because of rounding, to the pool of an active round or to the reserve.
+  */
+  sudo_rollover_roundRaw(args: {
+    round_id: RoundId;
+    target: RolloverTarget;
+  }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
+    return this.account.functionCall({contractId: this.contractId, methodName: "sudo_rollover_round", args, ...options});
+  }
+  /**
+  * Move matching funds of a finished round that no project can withdraw, because nobody
+  * co-voted or because of rounding, to the pool of an active round or to the reserve.
+  */
+  sudo_rollover_roundTx(args: {
+    round_id: RoundId;
+    target: RolloverTarget;
+  }, options?: ChangeMethodOptions): transactions.Action {
+    return transactions.functionCall("sudo_rollover_round", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
+  }
+  /**
+  * Add `amount` of the reserve in NEAR, or in `token_id`, to the pool of an active round.
+  */
+  async sudo_fund_round(args: {
+    round_id: RoundId;
+    amount: U128;
+    token_id?: AccountId;
+  }, options?: ChangeMethodOptions): Promise<Round> {
+    return providers.getTransactionLastResult(await this.sudo_fund_roundRaw(args, options));
+  }
+  /**
+  * Add `amount` of the reserve in NEAR, or in `token_id`, to the pool of an active round.
+  */
+  sudo_fund_roundRaw(args: {
+    round_id: RoundId;
+    amount: U128;
+    token_id?: AccountId;
+  }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
+    return this.account.functionCall({contractId: this.contractId, methodName: "sudo_fund_round", args, ...options});
+  }
+  /**
+  * Add `amount` of the reserve in NEAR, or in `token_id`, to the pool of an active round.
+  */
+  sudo_fund_roundTx(args: {
+    round_id: RoundId;
+    amount: U128;
+    token_id?: AccountId;
+  }, options?: ChangeMethodOptions): transactions.Action {
+    return transactions.functionCall("sudo_fund_round", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
+  }
+  /**
+  * Reserve in NEAR or in `token_id`.
+  */
+  get_reserve(args: {
+    token_id?: AccountId;
+  }, options?: ViewFunctionOptions): Promise<U128> {
+    return this.account.viewFunction(this.contractId, "get_reserve", args, options);
+  }
+  /**
+  * Schedules a round of default duration right after the last scheduled one,
+  * or starting now if the calendar is empty or over.
+  */
   async sudo_new_default_round(args = {}, options?: ChangeMethodOptions): Promise<Round> {
     return providers.getTransactionLastResult(await this.sudo_new_default_roundRaw(args, options));
   }
+  /**
+  * Schedules a round of default duration right after the last scheduled one,
+  * or starting now if the calendar is empty or over.
+  */
   sudo_new_default_roundRaw(args = {}, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
     return this.account.functionCall({contractId: this.contractId, methodName: "sudo_new_default_round", args, ...options});
   }
+  /**
+  * Schedules a round of default duration right after the last scheduled one,
+  * or starting now if the calendar is empty or over.
+  */
   sudo_new_default_roundTx(args = {}, options?: ChangeMethodOptions): transactions.Action {
     return transactions.functionCall("sudo_new_default_round", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
   }
-  async sudo_new_round(args: {
+  /**
+  * Creates a round on the calendar, it becomes the current round between
+  * `start_at` and `end_at` without further calls.
+  */
+  async sudo_schedule_round(args: {
     start_at: u32;
     end_at: u32;
+    matching?: MatchingStrategy;
+    params?: RoundParams;
   }, options?: ChangeMethodOptions): Promise<Round> {
-    return providers.getTransactionLastResult(await this.sudo_new_roundRaw(args, options));
+    return providers.getTransactionLastResult(await this.sudo_schedule_roundRaw(args, options));
   }
-  sudo_new_roundRaw(args: {
+  /**
+  * Creates a round on the calendar, it becomes the current round between
+  * `start_at` and `end_at` without further calls.
+  */
+  sudo_schedule_roundRaw(args: {
     start_at: u32;
     end_at: u32;
+    matching?: MatchingStrategy;
+    params?: RoundParams;
   }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
-    return this.account.functionCall({contractId: this.contractId, methodName: "sudo_new_round", args, ...options});
+    return this.account.functionCall({contractId: this.contractId, methodName: "sudo_schedule_round", args, ...options});
   }
-  sudo_new_roundTx(args: {
+  /**
+  * Creates a round on the calendar, it becomes the current round between
+  * `start_at` and `end_at` without further calls.
+  */
+  sudo_schedule_roundTx(args: {
     start_at: u32;
     end_at: u32;
+    matching?: MatchingStrategy;
+    params?: RoundParams;
+  }, options?: ChangeMethodOptions): transactions.Action {
+    return transactions.functionCall("sudo_schedule_round", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
+  }
+  /**
+  * Creates a round off the calendar, it never becomes the current round, so it has
+  * to be given by id to `donate`, `new_project` and views. See `sudo_schedule_round`.
+  */
+  async sudo_new_round(args: {
+    start_at: u32;
+    end_at: u32;
+    matching?: MatchingStrategy;
+    params?: RoundParams;
+  }, options?: ChangeMethodOptions): Promise<Round> {
+    return providers.getTransactionLastResult(await this.sudo_new_roundRaw(args, options));
+  }
+  /**
+  * Creates a round off the calendar, it never becomes the current round, so it has
+  * to be given by id to `donate`, `new_project` and views. See `sudo_schedule_round`.
+  */
+  sudo_new_roundRaw(args: {
+    start_at: u32;
+    end_at: u32;
+    matching?: MatchingStrategy;
+    params?: RoundParams;
+  }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
+    return this.account.functionCall({contractId: this.contractId, methodName: "sudo_new_round", args, ...options});
+  }
+  /**
+  * Creates a round off the calendar, it never becomes the current round, so it has
+  * to be given by id to `donate`, `new_project` and views. See `sudo_schedule_round`.
+  */
+  sudo_new_roundTx(args: {
+    start_at: u32;
+    end_at: u32;
+    matching?: MatchingStrategy;
+    params?: RoundParams;
   }, options?: ChangeMethodOptions): transactions.Action {
     return transactions.functionCall("sudo_new_round", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
   }
-  async sudo_update_current_round(args: {
+  /**
+  * Moves the voting window of an active round. The status only changes through
+  * `sudo_finish_round` and `sudo_cancel_round`, `end_at` is fixed once voting is over.
+  */
+  async sudo_update_round(args: {
+    round_id: RoundId;
     danger: boolean;
-    status?: RoundStatus;
     start_at?: u32;
     end_at?: u32;
   }, options?: ChangeMethodOptions): Promise<Round> {
-    return providers.getTransactionLastResult(await this.sudo_update_current_roundRaw(args, options));
+    return providers.getTransactionLastResult(await this.sudo_update_roundRaw(args, options));
   }
-  sudo_update_current_roundRaw(args: {
+  /**
+  * Moves the voting window of an active round. The status only changes through
+  * `sudo_finish_round` and `sudo_cancel_round`, `end_at` is fixed once voting is over.
+  */
+  sudo_update_roundRaw(args: {
+    round_id: RoundId;
     danger: boolean;
-    status?: RoundStatus;
     start_at?: u32;
     end_at?: u32;
   }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
-    return this.account.functionCall({contractId: this.contractId, methodName: "sudo_update_current_round", args, ...options});
+    return this.account.functionCall({contractId: this.contractId, methodName: "sudo_update_round", args, ...options});
   }
-  sudo_update_current_roundTx(args: {
+  /**
+  * Moves the voting window of an active round. The status only changes through
+  * `sudo_finish_round` and `sudo_cancel_round`, `end_at` is fixed once voting is over.
+  */
+  sudo_update_roundTx(args: {
+    round_id: RoundId;
     danger: boolean;
-    status?: RoundStatus;
     start_at?: u32;
     end_at?: u32;
   }, options?: ChangeMethodOptions): transactions.Action {
-    return transactions.functionCall("sudo_update_current_round", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
+    return transactions.functionCall("sudo_update_round", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
   }
-  async sudo_finish_current_round(args = {}, options?: ChangeMethodOptions): Promise<Round> {
-    return providers.getTransactionLastResult(await this.sudo_finish_current_roundRaw(args, options));
+  /**
+  * Writes results of up to `limit` more projects of a reviewed and tallied round.
+  * Call until the round is `Finished`, payouts start after that.
+  */
+  async sudo_finish_round(args: {
+    round_id: RoundId;
+    limit?: u32;
+  }, options?: ChangeMethodOptions): Promise<Round> {
+    return providers.getTransactionLastResult(await this.sudo_finish_roundRaw(args, options));
+  }
+  /**
+  * Writes results of up to `limit` more projects of a reviewed and tallied round.
+  * Call until the round is `Finished`, payouts start after that.
+  */
+  sudo_finish_roundRaw(args: {
+    round_id: RoundId;
+    limit?: u32;
+  }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
+    return this.account.functionCall({contractId: this.contractId, methodName: "sudo_finish_round", args, ...options});
+  }
+  /**
+  * Writes results of up to `limit` more projects of a reviewed and tallied round.
+  * Call until the round is `Finished`, payouts start after that.
+  */
+  sudo_finish_roundTx(args: {
+    round_id: RoundId;
+    limit?: u32;
+  }, options?: ChangeMethodOptions): transactions.Action {
+    return transactions.functionCall("sudo_finish_round", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
+  }
+  /**
+  * Accept `token_id` in the round with given vote cost, `None` stops accepting it.
+  */
+  async sudo_set_round_token(args: {
+    round_id: RoundId;
+    token_id: AccountId;
+    vote_cost?: U128;
+  }, options?: ChangeMethodOptions): Promise<Round> {
+    return providers.getTransactionLastResult(await this.sudo_set_round_tokenRaw(args, options));
+  }
+  /**
+  * Accept `token_id` in the round with given vote cost, `None` stops accepting it.
+  */
+  sudo_set_round_tokenRaw(args: {
+    round_id: RoundId;
+    token_id: AccountId;
+    vote_cost?: U128;
+  }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
+    return this.account.functionCall({contractId: this.contractId, methodName: "sudo_set_round_token", args, ...options});
+  }
+  /**
+  * Accept `token_id` in the round with given vote cost, `None` stops accepting it.
+  */
+  sudo_set_round_tokenTx(args: {
+    round_id: RoundId;
+    token_id: AccountId;
+    vote_cost?: U128;
+  }, options?: ChangeMethodOptions): transactions.Action {
+    return transactions.functionCall("sudo_set_round_token", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
   }
-  sudo_finish_current_roundRaw(args = {}, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
-    return this.account.functionCall({contractId: this.contractId, methodName: "sudo_finish_current_round", args, ...options});
+  /**
+  * Rounds accepting votes right now.
+  */
+  list_active_rounds(args = {}, options?: ViewFunctionOptions): Promise<Round[]> {
+    return this.account.viewFunction(this.contractId, "list_active_rounds", args, options);
   }
-  sudo_finish_current_roundTx(args = {}, options?: ChangeMethodOptions): transactions.Action {
-    return transactions.functionCall("sudo_finish_current_round", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
+  current_phase(args: {
+    round_id: RoundId;
+  }, options?: ViewFunctionOptions): Promise<RoundPhase> {
+    return this.account.viewFunction(this.contractId, "current_phase", args, options);
   }
+  /**
+  * Scheduled round accepting votes at the block time.
+  */
   get_current_round(args = {}, options?: ViewFunctionOptions): Promise<Round | null> {
     return this.account.viewFunction(this.contractId, "get_current_round", args, options);
   }
-  get_round(args: {
+  /**
+  * Rounds on the calendar ordered by `start_at`.
+  */
+  list_scheduled_rounds(args: {
+    limit?: u32;
+    offset?: u32;
+  }, options?: ViewFunctionOptions): Promise<Round[]> {
+    return this.account.viewFunction(this.contractId, "list_scheduled_rounds", args, options);
+  }
+  get_round(args: {
+    round_id: RoundId;
+  }, options?: ViewFunctionOptions): Promise<Round | null> {
+    return this.account.viewFunction(this.contractId, "get_round", args, options);
+  }
+  list_rounds(args: {
+    limit?: u32;
+    offset?: u32;
+  }, options?: ViewFunctionOptions): Promise<Round[]> {
+    return this.account.viewFunction(this.contractId, "list_rounds", args, options);
+  }
+  /**
+  * Adds the deposit to the matching pool of the round, the current one if not given.
+  */
+  async donate(args: {
+    round_id?: RoundId;
+  }, options?: ChangeMethodOptions): Promise<Round> {
+    return providers.getTransactionLastResult(await this.donateRaw(args, options));
+  }
+  /**
+  * Adds the deposit to the matching pool of the round, the current one if not given.
+  */
+  donateRaw(args: {
+    round_id?: RoundId;
+  }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
+    return this.account.functionCall({contractId: this.contractId, methodName: "donate", args, ...options});
+  }
+  /**
+  * Adds the deposit to the matching pool of the round, the current one if not given.
+  */
+  donateTx(args: {
+    round_id?: RoundId;
+  }, options?: ChangeMethodOptions): transactions.Action {
+    return transactions.functionCall("donate", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
+  }
+  async storage_deposit(args: {
+    account_id?: AccountId;
+    registration_only?: boolean;
+  }, options?: ChangeMethodOptions): Promise<StorageBalance> {
+    return providers.getTransactionLastResult(await this.storage_depositRaw(args, options));
+  }
+  storage_depositRaw(args: {
+    account_id?: AccountId;
+    registration_only?: boolean;
+  }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
+    return this.account.functionCall({contractId: this.contractId, methodName: "storage_deposit", args, ...options});
+  }
+  storage_depositTx(args: {
+    account_id?: AccountId;
+    registration_only?: boolean;
+  }, options?: ChangeMethodOptions): transactions.Action {
+    return transactions.functionCall("storage_deposit", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
+  }
+  async storage_withdraw(args: {
+    amount?: U128;
+  }, options?: ChangeMethodOptions): Promise<StorageBalance> {
+    return providers.getTransactionLastResult(await this.storage_withdrawRaw(args, options));
+  }
+  storage_withdrawRaw(args: {
+    amount?: U128;
+  }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
+    return this.account.functionCall({contractId: this.contractId, methodName: "storage_withdraw", args, ...options});
+  }
+  storage_withdrawTx(args: {
+    amount?: U128;
+  }, options?: ChangeMethodOptions): transactions.Action {
+    return transactions.functionCall("storage_withdraw", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
+  }
+  /**
+  * Accounts that still own projects or votes can not be unregistered, even with `force`.
+  */
+  async storage_unregister(args: {
+    force?: boolean;
+  }, options?: ChangeMethodOptions): Promise<boolean> {
+    return providers.getTransactionLastResult(await this.storage_unregisterRaw(args, options));
+  }
+  /**
+  * Accounts that still own projects or votes can not be unregistered, even with `force`.
+  */
+  storage_unregisterRaw(args: {
+    force?: boolean;
+  }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
+    return this.account.functionCall({contractId: this.contractId, methodName: "storage_unregister", args, ...options});
+  }
+  /**
+  * Accounts that still own projects or votes can not be unregistered, even with `force`.
+  */
+  storage_unregisterTx(args: {
+    force?: boolean;
+  }, options?: ChangeMethodOptions): transactions.Action {
+    return transactions.functionCall("storage_unregister", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
+  }
+  storage_balance_bounds(args = {}, options?: ViewFunctionOptions): Promise<StorageBalanceBounds> {
+    return this.account.viewFunction(this.contractId, "storage_balance_bounds", args, options);
+  }
+  storage_balance_of(args: {
+    account_id: AccountId;
+  }, options?: ViewFunctionOptions): Promise<StorageBalance | null> {
+    return this.account.viewFunction(this.contractId, "storage_balance_of", args, options);
+  }
+  /**
+  * Vote or donate with an accepted fungible token, returns unused amount.
+  */
+  async ft_on_transfer(args: {
+    sender_id: AccountId;
+    amount: U128;
+    msg: string;
+  }, options?: ChangeMethodOptions): Promise<U128> {
+    return providers.getTransactionLastResult(await this.ft_on_transferRaw(args, options));
+  }
+  /**
+  * Vote or donate with an accepted fungible token, returns unused amount.
+  */
+  ft_on_transferRaw(args: {
+    sender_id: AccountId;
+    amount: U128;
+    msg: string;
+  }, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
+    return this.account.functionCall({contractId: this.contractId, methodName: "ft_on_transfer", args, ...options});
+  }
+  /**
+  * Vote or donate with an accepted fungible token, returns unused amount.
+  */
+  ft_on_transferTx(args: {
+    sender_id: AccountId;
+    amount: U128;
+    msg: string;
+  }, options?: ChangeMethodOptions): transactions.Action {
+    return transactions.functionCall("ft_on_transfer", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
+  }
+  get_account_votes(args: {
+    account_id: AccountId;
+    round_id: RoundId;
+    limit?: u32;
+    offset?: u32;
+  }, options?: ViewFunctionOptions): Promise<AccountVote[]> {
+    return this.account.viewFunction(this.contractId, "get_account_votes", args, options);
+  }
+  /**
+  * Accounts which voted for the project with their votes.
+  */
+  get_project_voters(args: {
+    project_id: ProjectId;
+    limit?: u32;
+    offset?: u32;
+  }, options?: ViewFunctionOptions): Promise<ProjectVoter[]> {
+    return this.account.viewFunction(this.contractId, "get_project_voters", args, options);
+  }
+  /**
+  * Votes of the account in the round with what they paid per project.
+  */
+  get_account_contributions(args: {
+    account_id: AccountId;
+    round_id: RoundId;
+    limit?: u32;
+    offset?: u32;
+  }, options?: ViewFunctionOptions): Promise<AccountContribution[]> {
+    return this.account.viewFunction(this.contractId, "get_account_contributions", args, options);
+  }
+  async init(args = {}, options?: ChangeMethodOptions): Promise<void> {
+    return providers.getTransactionLastResult(await this.initRaw(args, options));
+  }
+  initRaw(args = {}, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
+    return this.account.functionCall({contractId: this.contractId, methodName: "init", args, ...options});
+  }
+  initTx(args = {}, options?: ChangeMethodOptions): transactions.Action {
+    return transactions.functionCall("init", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
+  }
+}
+/**
+* Contest an approved project during the review phase of its round,
+* `CHALLENGE_BOND` must be attached.
+* 
+* @contractMethod change
+*/
+export interface ChallengeProject {
+  args: {
+    project_id: ProjectId;
+    reason: string;
+  };
+  options: {
+    /** Units in gas
+    * @pattern [0-9]+
+    * @default "30000000000000"
+    */
+    gas?: string;
+    /** Units in yoctoNear
+    * @default "0"
+    */
+    attachedDeposit?: Balance;
+  }
+  
+}
+export type ChallengeProject__Result = Challenge;
+/**
+* Disqualify the challenged project and return the bond, or dismiss the challenge
+* and keep the bond as platform fee. Only can be called by owner or operator.
+* 
+* @contractMethod change
+*/
+export interface SudoResolveChallenge {
+  args: {
+    project_id: ProjectId;
+    disqualify: boolean;
+  };
+  options: {
+    /** Units in gas
+    * @pattern [0-9]+
+    * @default "30000000000000"
+    */
+    gas?: string;
+    /** Units in yoctoNear
+    * @default "0"
+    */
+    attachedDeposit?: Balance;
+  }
+  
+}
+export type SudoResolveChallenge__Result = Challenge;
+/**
+* 
+* @contractMethod view
+*/
+export interface GetChallenge {
+  args: {
+    project_id: ProjectId;
+  };
+  
+}
+export type GetChallenge__Result = Challenge | null;
+/**
+* Take back votes for a disqualified project, paid in NEAR or in `token_id`.
+* Platform fees are not refunded.
+* 
+* @contractMethod change
+*/
+export interface ClaimGrantRefund {
+  args: {
+    project_id: ProjectId;
+    token_id?: AccountId;
+  };
+  options: {
+    /** Units in gas
+    * @pattern [0-9]+
+    * @default "30000000000000"
+    */
+    gas?: string;
+    /** Units in yoctoNear
+    * @default "0"
+    */
+    attachedDeposit?: Balance;
+  }
+  
+}
+export type ClaimGrantRefund__Result = void;
+/**
+* 
+* @contractMethod view
+*/
+export interface GetConfig {
+  args: {};
+  
+}
+export type GetConfig__Result = Config;
+/**
+* 
+* @contractMethod change
+*/
+export interface SudoConfig {
+  args: {
+    fee_point?: u32;
+    default_duration?: u32;
+    default_vote_cost?: U128;
+    default_review_duration?: u32;
+  };
+  options: {
+    /** Units in gas
+    * @pattern [0-9]+
+    * @default "30000000000000"
+    */
+    gas?: string;
+    /** Units in yoctoNear
+    * @default "0"
+    */
+    attachedDeposit?: Balance;
+  }
+  
+}
+export type SudoConfig__Result = void;
+/**
+* Send collected platform fees in NEAR, or in `token_id` if given,
+* to `receiver_id` or to the treasury. Only can be called by owner.
+* 
+* @contractMethod change
+*/
+export interface SudoWithdrawFees {
+  args: {
+    amount: U128;
+    receiver_id?: AccountId;
+    token_id?: AccountId;
+  };
+  options: {
+    /** Units in gas
+    * @pattern [0-9]+
+    * @default "30000000000000"
+    */
+    gas?: string;
+    /** Units in yoctoNear
+    * @default "0"
+    */
+    attachedDeposit?: Balance;
+  }
+  
+}
+export type SudoWithdrawFees__Result = U128;
+/**
+* Collected platform fees in NEAR or in `token_id`.
+* 
+* @contractMethod view
+*/
+export interface GetFeeAmount {
+  args: {
+    token_id?: AccountId;
+  };
+  
+}
+export type GetFeeAmount__Result = U128;
+/**
+* Scores up to `limit` more projects of a `PairwiseBounded` round after voting ended, skipping
+* projects which are not approved. One call scores at most `TALLY_PAIRS_PER_CALL` pairs
+* of voters and continues a project where the last call stopped.
+* Call until `tallied_projects` reaches `projects`, matching is paid out after that.
+* 
+* @contractMethod change
+*/
+export interface SudoTallyRound {
+  args: {
+    round_id: RoundId;
+    limit?: u32;
+  };
+  options: {
+    /** Units in gas
+    * @pattern [0-9]+
+    * @default "30000000000000"
+    */
+    gas?: string;
+    /** Units in yoctoNear
+    * @default "0"
+    */
+    attachedDeposit?: Balance;
+  }
+  
+}
+export type SudoTallyRound__Result = Round;
+/**
+* Moves at most `limit` entries of the old state, `MIGRATE_ENTRIES_PER_CALL` by default.
+* Call until it returns true, calls which read old state fail with `ERR_MIGRATION_PENDING`
+* meanwhile.
+* 
+* @contractMethod change
+*/
+export interface SudoMigrate {
+  args: {
+    limit?: u32;
+  };
+  options: {
+    /** Units in gas
+    * @pattern [0-9]+
+    * @default "30000000000000"
+    */
+    gas?: string;
+    /** Units in yoctoNear
+    * @default "0"
+    */
+    attachedDeposit?: Balance;
+  }
+  
+}
+export type SudoMigrate__Result = boolean;
+/**
+* 
+* @contractMethod view
+*/
+export interface IsMigrated {
+  args: {};
+  
+}
+export type IsMigrated__Result = boolean;
+/**
+* 
+* @contractMethod change
+*/
+export interface SetOwner {
+  args: {
+    new_owner_id: AccountId;
+  };
+  options: {
+    /** Units in gas
+    * @pattern [0-9]+
+    * @default "30000000000000"
+    */
+    gas?: string;
+    /** Units in yoctoNear
+    * @default "0"
+    */
+    attachedDeposit?: Balance;
+  }
+  
+}
+export type SetOwner__Result = void;
+/**
+* Set account that receives platform fees. Only can be called by owner.
+* 
+* @contractMethod change
+*/
+export interface SetTreasury {
+  args: {
+    treasury_id: AccountId;
+  };
+  options: {
+    /** Units in gas
+    * @pattern [0-9]+
+    * @default "30000000000000"
+    */
+    gas?: string;
+    /** Units in yoctoNear
+    * @default "0"
+    */
+    attachedDeposit?: Balance;
+  }
+  
+}
+export type SetTreasury__Result = void;
+/**
+* Get operators
+* 
+* @contractMethod view
+*/
+export interface Operators {
+  args: {};
+  
+}
+export type Operators__Result = AccountId[];
+/**
+* Extend operators. Only can be called by owner.
+* 
+* @contractMethod change
+*/
+export interface ExtendOperators {
+  args: {
+    operators: AccountId[];
+  };
+  options: {
+    /** Units in gas
+    * @pattern [0-9]+
+    * @default "30000000000000"
+    */
+    gas?: string;
+    /** Units in yoctoNear
+    * @default "0"
+    */
+    attachedDeposit?: Balance;
+  }
+  
+}
+export type ExtendOperators__Result = void;
+/**
+* Remove operators. Only can be called by owner.
+* 
+* @contractMethod change
+*/
+export interface RemoveOperators {
+  args: {
+    operators: AccountId[];
+  };
+  options: {
+    /** Units in gas
+    * @pattern [0-9]+
+    * @default "30000000000000"
+    */
+    gas?: string;
+    /** Units in yoctoNear
+    * @default "0"
+    */
+    attachedDeposit?: Balance;
+  }
+  
+}
+export type RemoveOperators__Result = void;
+/**
+* Set seconds between staging code and deploying it. Only can be called by owner.
+* The delay only can go up, lowering it takes an upgrade which waits the current delay.
+* 
+* @contractMethod change
+*/
+export interface SetUpgradeDelay {
+  args: {
+    upgrade_delay: u32;
+  };
+  options: {
+    /** Units in gas
+    * @pattern [0-9]+
+    * @default "30000000000000"
+    */
+    gas?: string;
+    /** Units in yoctoNear
+    * @default "0"
+    */
+    attachedDeposit?: Balance;
+  }
+  
+}
+export type SetUpgradeDelay__Result = void;
+/**
+* Store new contract code to be deployed after the upgrade delay,
+* replacing code staged before. Only can be called by owner.
+* 
+* @contractMethod change
+*/
+export interface StageUpgrade {
+  args: {
+    code: Base64VecU8;
+  };
+  options: {
+    /** Units in gas
+    * @pattern [0-9]+
+    * @default "30000000000000"
+    */
+    gas?: string;
+    /** Units in yoctoNear
+    * @default "0"
+    */
+    attachedDeposit?: Balance;
+  }
+  
+}
+export type StageUpgrade__Result = StagedUpgrade;
+/**
+* Deploy staged code and call `migrate` on it. Only can be called by owner.
+* 
+* @contractMethod change
+*/
+export interface DeployUpgrade {
+  args: {};
+  options: {
+    /** Units in gas
+    * @pattern [0-9]+
+    * @default "30000000000000"
+    */
+    gas?: string;
+    /** Units in yoctoNear
+    * @default "0"
+    */
+    attachedDeposit?: Balance;
+  }
+  
+}
+export type DeployUpgrade__Result = void;
+/**
+* 
+* @contractMethod view
+*/
+export interface GetStagedUpgrade {
+  args: {};
+  
+}
+export type GetStagedUpgrade__Result = StagedUpgrade | null;
+/**
+* Applies to the round, the scheduled one taking applications if not given,
+* the project waits for review.
+* 
+* @contractMethod change
+*/
+export interface NewProject {
+  args: {
+    round_id?: RoundId;
+    name: string;
+    description: string;
+    external_url: string;
+    image: string;
+  };
+  options: {
+    /** Units in gas
+    * @pattern [0-9]+
+    * @default "30000000000000"
+    */
+    gas?: string;
+    /** Units in yoctoNear
+    * @default "0"
+    */
+    attachedDeposit?: Balance;
+  }
+  
+}
+export type NewProject__Result = Project;
+/**
+* Returns withdrawable and total granted amounts in NEAR or in `token_id`.
+* 
+* @contractMethod view
+*/
+export interface GrantFor {
+  args: {
+    project_id: ProjectId;
+    token_id?: AccountId;
+  };
+  
+}
+export type GrantFor__Result = [U128, U128];
+/**
+* Withdraw NEAR, or `token_id` if given, to the project payout account.
+* 
+* @contractMethod change
+*/
+export interface Withdraw {
+  args: {
+    project_id: ProjectId;
+    amount: U128;
+    token_id?: AccountId;
+  };
+  options: {
+    /** Units in gas
+    * @pattern [0-9]+
+    * @default "30000000000000"
+    */
+    gas?: string;
+    /** Units in yoctoNear
+    * @default "0"
+    */
+    attachedDeposit?: Balance;
+  }
+  
+}
+export type Withdraw__Result = U128;
+/**
+* Set account that receives payouts, `None` pays to the project owner.
+* Only can be called by project owner.
+* 
+* @contractMethod change
+*/
+export interface SetProjectBeneficiary {
+  args: {
+    project_id: ProjectId;
+    beneficiary?: AccountId;
+  };
+  options: {
+    /** Units in gas
+    * @pattern [0-9]+
+    * @default "30000000000000"
+    */
+    gas?: string;
+    /** Units in yoctoNear
+    * @default "0"
+    */
+    attachedDeposit?: Balance;
+  }
+  
+}
+export type SetProjectBeneficiary__Result = Project;
+/**
+* Set accounts allowed to call `withdraw` besides the owner.
+* Only can be called by project owner.
+* 
+* @contractMethod change
+*/
+export interface SetProjectDelegates {
+  args: {
+    project_id: ProjectId;
+    delegates: AccountId[];
+  };
+  options: {
+    /** Units in gas
+    * @pattern [0-9]+
+    * @default "30000000000000"
+    */
+    gas?: string;
+    /** Units in yoctoNear
+    * @default "0"
+    */
+    attachedDeposit?: Balance;
+  }
+  
+}
+export type SetProjectDelegates__Result = Project;
+/**
+* Approve a pending project, it takes votes and gets matched from now on.
+* Only can be called by owner or operators while the round is open.
+* 
+* @contractMethod change
+*/
+export interface SudoApproveProject {
+  args: {
+    project_id: ProjectId;
+    reason?: string;
+  };
+  options: {
+    /** Units in gas
+    * @pattern [0-9]+
+    * @default "30000000000000"
+    */
+    gas?: string;
+    /** Units in yoctoNear
+    * @default "0"
+    */
+    attachedDeposit?: Balance;
+  }
+  
+}
+export type SudoApproveProject__Result = Project;
+/**
+* Reject a pending project. Only can be called by owner or operators.
+* 
+* @contractMethod change
+*/
+export interface SudoRejectProject {
+  args: {
+    project_id: ProjectId;
+    reason: string;
+  };
+  options: {
+    /** Units in gas
+    * @pattern [0-9]+
+    * @default "30000000000000"
+    */
+    gas?: string;
+    /** Units in yoctoNear
+    * @default "0"
+    */
+    attachedDeposit?: Balance;
+  }
+  
+}
+export type SudoRejectProject__Result = Project;
+/**
+* Update project metadata while its round is open, previous values are kept
+* in the revision history. Only can be called by project owner.
+* 
+* @contractMethod change
+*/
+export interface UpdateProject {
+  args: {
+    project_id: ProjectId;
+    name?: string;
+    description?: string;
+    external_url?: string;
+    image?: string;
+  };
+  options: {
+    /** Units in gas
+    * @pattern [0-9]+
+    * @default "30000000000000"
+    */
+    gas?: string;
+    /** Units in yoctoNear
+    * @default "0"
+    */
+    attachedDeposit?: Balance;
+  }
+  
+}
+export type UpdateProject__Result = Project;
+/**
+* Past metadata of the project, oldest first.
+* 
+* @contractMethod view
+*/
+export interface GetProjectRevisions {
+  args: {
+    project_id: ProjectId;
+  };
+  
+}
+export type GetProjectRevisions__Result = ProjectRevision[];
+/**
+* 
+* @contractMethod view
+*/
+export interface GetProject {
+  args: {
+    project_id: ProjectId;
+  };
+  
+}
+export type GetProject__Result = Project | null;
+/**
+* Projects of the round, only ones with `status` if given.
+* 
+* @contractMethod view
+*/
+export interface ListProjects {
+  args: {
+    round_id: RoundId;
+    limit?: u32;
+    offset?: u32;
+    status?: ProjectStatus;
+  };
+  
+}
+export type ListProjects__Result = Project[];
+/**
+* 
+* @contractMethod view
+*/
+export interface ProjectsForOwner {
+  args: {
+    owner_id: AccountId;
+    limit?: u32;
+    offset?: u32;
+  };
+  
+}
+export type ProjectsForOwner__Result = Project[];
+/**
+* Vote for the project paying with attached NEAR, the excess is refunded.
+* When the round has a registry, the vote is applied once the registry confirms
+* that the voter is eligible and resolves to `null` with a full refund otherwise.
+* 
+* @contractMethod change
+*/
+export interface Vote {
+  args: {
+    project_id: ProjectId;
+    votes: u64;
+  };
+  options: {
+    /** Units in gas
+    * @pattern [0-9]+
+    * @default "30000000000000"
+    */
+    gas?: string;
+    /** Units in yoctoNear
+    * @default "0"
+    */
+    attachedDeposit?: Balance;
+  }
+  
+}
+export type Vote__Result = Project;
+/**
+* Projects of the round ordered by `sort_by`, starting after `from_key`.
+* Every page costs the same whatever its depth.
+* 
+* @contractMethod view
+*/
+export interface ListProjectsV2 {
+  args: {
+    round_id: RoundId;
+    sort_by: ProjectSort;
+    from_key?: ProjectCursor;
+    limit?: u32;
+  };
+  
+}
+export type ListProjectsV2__Result = ProjectPage;
+/**
+* Abort an unfinished round, nothing is paid out to projects and voters and donors
+* can claim refunds including platform fees. Funds added by `sudo_fund_round` or a rollover go to the reserve
+* and bonds of open challenges go back. Only can be called by owner.
+* 
+* @contractMethod change
+*/
+export interface SudoCancelRound {
+  args: {
+    round_id: RoundId;
+  };
+  options: {
+    /** Units in gas
+    * @pattern [0-9]+
+    * @default "30000000000000"
+    */
+    gas?: string;
+    /** Units in yoctoNear
+    * @default "0"
+    */
+    attachedDeposit?: Balance;
+  }
+  
+}
+export type SudoCancelRound__Result = Round;
+/**
+* Take back votes and donations to a cancelled round with their platform fees,
+* paid in NEAR or in `token_id`.
+* 
+* @contractMethod change
+*/
+export interface ClaimRefund {
+  args: {
+    round_id: RoundId;
+    token_id?: AccountId;
+  };
+  options: {
+    /** Units in gas
+    * @pattern [0-9]+
+    * @default "30000000000000"
+    */
+    gas?: string;
+    /** Units in yoctoNear
+    * @default "0"
+    */
+    attachedDeposit?: Balance;
+  }
+  
+}
+export type ClaimRefund__Result = void;
+/**
+* Set the registry that confirms voters of the round, `None` lets everyone vote.
+* 
+* @contractMethod change
+*/
+export interface SudoSetRoundRegistry {
+  args: {
     round_id: RoundId;
-  }, options?: ViewFunctionOptions): Promise<Round | null> {
-    return this.account.viewFunction(this.contractId, "get_round", args, options);
+    registry_id?: AccountId;
+  };
+  options: {
+    /** Units in gas
+    * @pattern [0-9]+
+    * @default "30000000000000"
+    */
+    gas?: string;
+    /** Units in yoctoNear
+    * @default "0"
+    */
+    attachedDeposit?: Balance;
   }
-  list_rounds(args: {
+  
+}
+export type SudoSetRoundRegistry__Result = Round;
+/**
+* Results of a finished round ordered by rank.
+* 
+* @contractMethod view
+*/
+export interface GetRoundResults {
+  args: {
+    round_id: RoundId;
     limit?: u32;
     offset?: u32;
-  }, options?: ViewFunctionOptions): Promise<Round[]> {
-    return this.account.viewFunction(this.contractId, "list_rounds", args, options);
-  }
-  async donate(args = {}, options?: ChangeMethodOptions): Promise<Round> {
-    return providers.getTransactionLastResult(await this.donateRaw(args, options));
-  }
-  donateRaw(args = {}, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
-    return this.account.functionCall({contractId: this.contractId, methodName: "donate", args, ...options});
-  }
-  donateTx(args = {}, options?: ChangeMethodOptions): transactions.Action {
-    return transactions.functionCall("donate", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
-  }
-  async init(args = {}, options?: ChangeMethodOptions): Promise<void> {
-    return providers.getTransactionLastResult(await this.initRaw(args, options));
-  }
-  initRaw(args = {}, options?: ChangeMethodOptions): Promise<providers.FinalExecutionOutcome> {
-    return this.account.functionCall({contractId: this.contractId, methodName: "init", args, ...options});
-  }
-  initTx(args = {}, options?: ChangeMethodOptions): transactions.Action {
-    return transactions.functionCall("init", args, options?.gas ?? DEFAULT_FUNCTION_CALL_GAS, options?.attachedDeposit ?? new BN(0))
-  }
+  };
+  
 }
+export type GetRoundResults__Result = RoundResult[];
 /**
 * 
 * @contractMethod view
 */
-export interface GetConfig {
-  args: {};
+export interface GetRoundResult {
+  args: {
+    project_id: ProjectId;
+  };
   
 }
-export type GetConfig__Result = Config;
+export type GetRoundResult__Result = RoundResult | null;
 /**
+* Move matching funds of a finished round that no project can withdraw, because nobody
+* co-voted or because of rounding, to the pool of an active round or to the reserve.
 * 
 * @contractMethod change
 */
-export interface SudoConfig {
+export interface SudoRolloverRound {
   args: {
-    fee_point?: u32;
-    default_duration?: u32;
-    default_vote_cost?: U128;
+    round_id: RoundId;
+    target: RolloverTarget;
   };
   options: {
     /** Units in gas
@@ -414,14 +2489,17 @@ export interface SudoConfig {
   }
   
 }
-export type SudoConfig__Result = void;
+export type SudoRolloverRound__Result = Round;
 /**
+* Add `amount` of the reserve in NEAR, or in `token_id`, to the pool of an active round.
 * 
 * @contractMethod change
 */
-export interface SetOwner {
+export interface SudoFundRound {
   args: {
-    new_owner_id: AccountId;
+    round_id: RoundId;
+    amount: U128;
+    token_id?: AccountId;
   };
   options: {
     /** Units in gas
@@ -436,25 +2514,53 @@ export interface SetOwner {
   }
   
 }
-export type SetOwner__Result = void;
+export type SudoFundRound__Result = Round;
 /**
-* Get operators
+* Reserve in NEAR or in `token_id`.
 * 
 * @contractMethod view
 */
-export interface Operators {
+export interface GetReserve {
+  args: {
+    token_id?: AccountId;
+  };
+  
+}
+export type GetReserve__Result = U128;
+/**
+* Schedules a round of default duration right after the last scheduled one,
+* or starting now if the calendar is empty or over.
+* 
+* @contractMethod change
+*/
+export interface SudoNewDefaultRound {
   args: {};
+  options: {
+    /** Units in gas
+    * @pattern [0-9]+
+    * @default "30000000000000"
+    */
+    gas?: string;
+    /** Units in yoctoNear
+    * @default "0"
+    */
+    attachedDeposit?: Balance;
+  }
   
 }
-export type Operators__Result = AccountId[];
+export type SudoNewDefaultRound__Result = Round;
 /**
-* Extend operators. Only can be called by owner.
+* Creates a round on the calendar, it becomes the current round between
+* `start_at` and `end_at` without further calls.
 * 
 * @contractMethod change
 */
-export interface ExtendOperators {
+export interface SudoScheduleRound {
   args: {
-    operators: AccountId[];
+    start_at: u32;
+    end_at: u32;
+    matching?: MatchingStrategy;
+    params?: RoundParams;
   };
   options: {
     /** Units in gas
@@ -469,15 +2575,19 @@ export interface ExtendOperators {
   }
   
 }
-export type ExtendOperators__Result = void;
+export type SudoScheduleRound__Result = Round;
 /**
-* Remove operators. Only can be called by owner.
+* Creates a round off the calendar, it never becomes the current round, so it has
+* to be given by id to `donate`, `new_project` and views. See `sudo_schedule_round`.
 * 
 * @contractMethod change
 */
-export interface RemoveOperators {
+export interface SudoNewRound {
   args: {
-    operators: AccountId[];
+    start_at: u32;
+    end_at: u32;
+    matching?: MatchingStrategy;
+    params?: RoundParams;
   };
   options: {
     /** Units in gas
@@ -492,17 +2602,19 @@ export interface RemoveOperators {
   }
   
 }
-export type RemoveOperators__Result = void;
+export type SudoNewRound__Result = Round;
 /**
+* Moves the voting window of an active round. The status only changes through
+* `sudo_finish_round` and `sudo_cancel_round`, `end_at` is fixed once voting is over.
 * 
 * @contractMethod change
 */
-export interface NewProject {
+export interface SudoUpdateRound {
   args: {
-    name: string;
-    description: string;
-    external_url: string;
-    image: string;
+    round_id: RoundId;
+    danger: boolean;
+    start_at?: u32;
+    end_at?: u32;
   };
   options: {
     /** Units in gas
@@ -517,26 +2629,42 @@ export interface NewProject {
   }
   
 }
-export type NewProject__Result = Project;
+export type SudoUpdateRound__Result = Round;
 /**
+* Writes results of up to `limit` more projects of a reviewed and tallied round.
+* Call until the round is `Finished`, payouts start after that.
 * 
-* @contractMethod view
+* @contractMethod change
 */
-export interface GrantFor {
+export interface SudoFinishRound {
   args: {
-    project_id: ProjectId;
+    round_id: RoundId;
+    limit?: u32;
   };
+  options: {
+    /** Units in gas
+    * @pattern [0-9]+
+    * @default "30000000000000"
+    */
+    gas?: string;
+    /** Units in yoctoNear
+    * @default "0"
+    */
+    attachedDeposit?: Balance;
+  }
   
 }
-export type GrantFor__Result = U128;
+export type SudoFinishRound__Result = Round;
 /**
+* Accept `token_id` in the round with given vote cost, `None` stops accepting it.
 * 
 * @contractMethod change
 */
-export interface Withdraw {
+export interface SudoSetRoundToken {
   args: {
-    project_id: ProjectId;
-    amount: U128;
+    round_id: RoundId;
+    token_id: AccountId;
+    vote_cost?: U128;
   };
   options: {
     /** Units in gas
@@ -551,51 +2679,82 @@ export interface Withdraw {
   }
   
 }
-export type Withdraw__Result = void;
+export type SudoSetRoundToken__Result = Round;
 /**
+* Rounds accepting votes right now.
 * 
 * @contractMethod view
 */
-export interface GetProject {
+export interface ListActiveRounds {
+  args: {};
+  
+}
+export type ListActiveRounds__Result = Round[];
+/**
+* 
+* @contractMethod view
+*/
+export interface CurrentPhase {
   args: {
-    project_id: ProjectId;
+    round_id: RoundId;
   };
   
 }
-export type GetProject__Result = Project | null;
+export type CurrentPhase__Result = RoundPhase;
 /**
+* Scheduled round accepting votes at the block time.
 * 
 * @contractMethod view
 */
-export interface ListProjects {
+export interface GetCurrentRound {
+  args: {};
+  
+}
+export type GetCurrentRound__Result = Round | null;
+/**
+* Rounds on the calendar ordered by `start_at`.
+* 
+* @contractMethod view
+*/
+export interface ListScheduledRounds {
   args: {
     limit?: u32;
     offset?: u32;
   };
   
 }
-export type ListProjects__Result = Project[];
+export type ListScheduledRounds__Result = Round[];
 /**
 * 
 * @contractMethod view
 */
-export interface ProjectsForOwner {
+export interface GetRound {
+  args: {
+    round_id: RoundId;
+  };
+  
+}
+export type GetRound__Result = Round | null;
+/**
+* 
+* @contractMethod view
+*/
+export interface ListRounds {
   args: {
-    owner_id: AccountId;
     limit?: u32;
     offset?: u32;
   };
   
 }
-export type ProjectsForOwner__Result = Project[];
+export type ListRounds__Result = Round[];
 /**
+* Adds the deposit to the matching pool of the round, the current one if not given.
 * 
 * @contractMethod change
 */
-export interface Vote {
+export interface Donate {
   args: {
-    project_id: ProjectId;
-    votes: u64;
+    round_id?: RoundId;
   };
   options: {
     /** Units in gas
@@ -610,13 +2769,16 @@ export interface Vote {
   }
   
 }
-export type Vote__Result = Project;
+export type Donate__Result = Round;
 /**
 * 
 * @contractMethod change
 */
-export interface SudoNewDefaultRound {
-  args: {};
+export interface StorageDeposit {
+  args: {
+    account_id?: AccountId;
+    registration_only?: boolean;
+  };
   options: {
     /** Units in gas
     * @pattern [0-9]+
@@ -630,15 +2792,14 @@ export interface SudoNewDefaultRound {
   }
   
 }
-export type SudoNewDefaultRound__Result = Round;
+export type StorageDeposit__Result = StorageBalance;
 /**
 * 
 * @contractMethod change
 */
-export interface SudoNewRound {
+export interface StorageWithdraw {
   args: {
-    start_at: u32;
-    end_at: u32;
+    amount?: U128;
   };
   options: {
     /** Units in gas
@@ -653,17 +2814,15 @@ export interface SudoNewRound {
   }
   
 }
-export type SudoNewRound__Result = Round;
+export type StorageWithdraw__Result = StorageBalance;
 /**
+* Accounts that still own projects or votes can not be unregistered, even with `force`.
 * 
 * @contractMethod change
 */
-export interface SudoUpdateCurrentRound {
+export interface StorageUnregister {
   args: {
-    danger: boolean;
-    status?: RoundStatus;
-    start_at?: u32;
-    end_at?: u32;
+    force?: boolean;
   };
   options: {
     /** Units in gas
@@ -678,13 +2837,38 @@ export interface SudoUpdateCurrentRound {
   }
   
 }
-export type SudoUpdateCurrentRound__Result = Round;
+export type StorageUnregister__Result = boolean;
 /**
 * 
-* @contractMethod change
+* @contractMethod view
 */
-export interface SudoFinishCurrentRound {
+export interface StorageBalanceBounds {
   args: {};
+  
+}
+export type StorageBalanceBounds__Result = StorageBalanceBounds;
+/**
+* 
+* @contractMethod view
+*/
+export interface StorageBalanceOf {
+  args: {
+    account_id: AccountId;
+  };
+  
+}
+export type StorageBalanceOf__Result = StorageBalance | null;
+/**
+* Vote or donate with an accepted fungible token, returns unused amount.
+* 
+* @contractMethod change
+*/
+export interface FtOnTransfer {
+  args: {
+    sender_id: AccountId;
+    amount: U128;
+    msg: string;
+  };
   options: {
     /** Units in gas
     * @pattern [0-9]+
@@ -698,59 +2882,50 @@ export interface SudoFinishCurrentRound {
   }
   
 }
-export type SudoFinishCurrentRound__Result = Round;
-/**
-* 
-* @contractMethod view
-*/
-export interface GetCurrentRound {
-  args: {};
-  
-}
-export type GetCurrentRound__Result = Round | null;
+export type FtOnTransfer__Result = U128;
 /**
 * 
 * @contractMethod view
 */
-export interface GetRound {
+export interface GetAccountVotes {
   args: {
+    account_id: AccountId;
     round_id: RoundId;
+    limit?: u32;
+    offset?: u32;
   };
   
 }
-export type GetRound__Result = Round | null;
+export type GetAccountVotes__Result = AccountVote[];
 /**
+* Accounts which voted for the project with their votes.
 * 
 * @contractMethod view
 */
-export interface ListRounds {
+export interface GetProjectVoters {
   args: {
+    project_id: ProjectId;
     limit?: u32;
     offset?: u32;
   };
   
 }
-export type ListRounds__Result = Round[];
+export type GetProjectVoters__Result = ProjectVoter[];
 /**
+* Votes of the account in the round with what they paid per project.
 * 
-* @contractMethod change
+* @contractMethod view
 */
-export interface Donate {
-  args: {};
-  options: {
-    /** Units in gas
-    * @pattern [0-9]+
-    * @default "30000000000000"
-    */
-    gas?: string;
-    /** Units in yoctoNear
-    * @default "0"
-    */
-    attachedDeposit?: Balance;
-  }
+export interface GetAccountContributions {
+  args: {
+    account_id: AccountId;
+    round_id: RoundId;
+    limit?: u32;
+    offset?: u32;
+  };
   
 }
-export type Donate__Result = Round;
+export type GetAccountContributions__Result = AccountContribution[];
 /**
 * 
 * @contractMethod change